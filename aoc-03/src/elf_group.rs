@@ -1,34 +1,82 @@
 use crate::rucksack::Rucksack;
 use crate::rucksack_item::RucksackItem;
+use crate::rucksack_item_set::RucksackItemSet;
 
 pub struct ElfGroup {
-    rucksacks: [Rucksack; 3],
+    rucksacks: Vec<Rucksack>,
 }
 
 impl ElfGroup {
-    pub fn sum_misplaced_items(&self) -> u64 {
-        self.rucksacks.iter().fold(0, |acc, val| {
-            acc + val.find_misplaced_item().get_priority() as u64
-        })
+    /// Sums the priority of each rucksack's misplaced item, or `None` if any rucksack doesn't have exactly one.
+    pub fn sum_misplaced_items(&self) -> Option<u64> {
+        self.rucksacks
+            .iter()
+            .map(|r| r.find_misplaced_item().map(|i| i.get_priority() as u64))
+            .sum()
     }
 
-    pub fn find_badge(&self) -> RucksackItem {
-        for item in self.rucksacks[0].iter() {
-            if self.rucksacks[1].contains(item) && self.rucksacks[2].contains(item) {
-                return *item;
-            }
-        }
-        panic!("No suitable badge found!")
+    /// Returns every item type carried by all members of the group.
+    pub fn find_badges(&self) -> RucksackItemSet {
+        let items: Vec<RucksackItemSet> = self.rucksacks.iter().map(Rucksack::items).collect();
+        RucksackItemSet::intersection_of(&items)
+    }
+
+    /// Returns the item type carried by all members of the group, or `None` if there isn't exactly one.
+    pub fn find_badge(&self) -> Option<RucksackItem> {
+        self.find_badges().single()
     }
 }
 
 impl From<&[&str]> for ElfGroup {
     fn from(input: &[&str]) -> Self {
-        let rucksacks = [
-            Rucksack::from(input[0]),
-            Rucksack::from(input[1]),
-            Rucksack::from(input[2]),
-        ];
+        let rucksacks = input.iter().map(|l| Rucksack::from(*l)).collect();
         ElfGroup { rucksacks }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::elf_group::ElfGroup;
+    use crate::rucksack_item::RucksackItem;
+
+    const EXAMPLE: [&str; 6] = [
+        "vJrwpWtwJgWrhcsFMMfFFhFp",
+        "jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL",
+        "PmmdzqPrVvPwwTWBwg",
+        "wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn",
+        "ttgJtRGJQctTZtZT",
+        "CrZsJsPPZsGzwwsLwLmpwMDw",
+    ];
+
+    #[test]
+    fn sums_misplaced_items() {
+        let group = ElfGroup::from(&EXAMPLE[0..3]);
+        assert_eq!(group.sum_misplaced_items(), Some(16 + 38 + 42));
+    }
+
+    #[test]
+    fn finds_badge() {
+        let first = ElfGroup::from(&EXAMPLE[0..3]);
+        assert_eq!(first.find_badge(), Some(RucksackItem::from('r')));
+        let second = ElfGroup::from(&EXAMPLE[3..6]);
+        assert_eq!(second.find_badge(), Some(RucksackItem::from('Z')));
+    }
+
+    #[test]
+    fn supports_any_group_size() {
+        let pair = ElfGroup::from(&EXAMPLE[0..2]);
+        let badges: String = pair.find_badges().iter().map(char::from).collect();
+        assert_eq!(badges, "frsFM");
+        assert_eq!(pair.find_badge(), None);
+
+        let single = ElfGroup::from(&EXAMPLE[4..5]);
+        assert_eq!(single.find_badges().len(), 9);
+    }
+
+    #[test]
+    fn empty_group_has_no_badge() {
+        let empty = ElfGroup::from(&EXAMPLE[0..0]);
+        assert_eq!(empty.find_badge(), None);
+        assert_eq!(empty.sum_misplaced_items(), Some(0));
+    }
+}
@@ -2,27 +2,27 @@ mod elf_group;
 mod rucksack;
 mod rucksack_compartment;
 mod rucksack_item;
+mod rucksack_item_set;
+mod rucksack_report;
 
-use crate::elf_group::ElfGroup;
+use crate::rucksack_report::RucksackReport;
 use aoc_core::puzzle_input::PuzzleInput;
 
 fn main() {
     let input = PuzzleInput::default();
-    let groups: Vec<ElfGroup> = input
-        .to_lines()
-        .collect::<Vec<&str>>()
-        .chunks(3)
-        .map(ElfGroup::from)
-        .collect();
-    let sum_misplaced = groups
-        .iter()
-        .map(|r| r.sum_misplaced_items())
-        .fold(0u64, |acc, val| acc + val);
+    let lines: Vec<&str> = input.to_lines().collect();
+    let report = RucksackReport::new(&lines, 3);
+    if !report.is_valid() {
+        println!("Input has {} issues:", report.issues().len());
+        for issue in report.issues() {
+            println!("  {}", issue);
+        }
+        return;
+    }
+
+    let sum_misplaced = report.sum_misplaced_items().unwrap();
     println!("Sum of misplaced items priorities: {}", sum_misplaced);
 
-    let sum_badges = groups
-        .iter()
-        .map(|x| x.find_badge().get_priority())
-        .fold(0u64, |acc, val| acc + val as u64);
+    let sum_badges = report.sum_badges().unwrap();
     println!("Sum of badge priorities: {}", sum_badges);
 }
@@ -1,5 +1,6 @@
 use crate::rucksack_compartment::RucksackCompartment;
 use crate::rucksack_item::RucksackItem;
+use crate::rucksack_item_set::RucksackItemSet;
 
 pub struct Rucksack {
     left: RucksackCompartment,
@@ -7,27 +8,24 @@ pub struct Rucksack {
 }
 
 impl Rucksack {
-    pub fn find_misplaced_item(&self) -> RucksackItem {
-        for item in self.left.iter() {
-            if self.right.contains(item) {
-                return *item;
-            }
-        }
-        panic!("No item found in both compartments!")
+    /// Returns every item type that appears in both compartments.
+    pub fn find_misplaced_items(&self) -> RucksackItemSet {
+        self.left.items().intersection(self.right.items())
     }
 
-    pub fn contains(&self, item: &RucksackItem) -> bool {
-        self.left.contains(item) || self.right.contains(item)
+    /// Returns the item type that appears in both compartments, or `None` if there isn't exactly one.
+    pub fn find_misplaced_item(&self) -> Option<RucksackItem> {
+        self.find_misplaced_items().single()
     }
 
-    pub fn iter(&self) -> impl Iterator<Item = &RucksackItem> {
-        self.left.iter().chain(self.right.iter())
+    pub fn items(&self) -> RucksackItemSet {
+        self.left.items().union(self.right.items())
     }
 }
 
 impl From<&str> for Rucksack {
     fn from(input: &str) -> Self {
-        let midpoint = input.len() / 2; // Assumes always even
+        let midpoint = input.len() / 2; // Odd lengths are reported by RucksackReport
         let left = input[0..midpoint].chars().into();
         let right = input[midpoint..].chars().into();
 
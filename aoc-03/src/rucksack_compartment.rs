@@ -1,16 +1,13 @@
 use crate::rucksack_item::RucksackItem;
+use crate::rucksack_item_set::RucksackItemSet;
 
 pub struct RucksackCompartment {
-    contents: Vec<RucksackItem>,
+    contents: RucksackItemSet,
 }
 
 impl RucksackCompartment {
-    pub fn iter(&self) -> impl Iterator<Item = &RucksackItem> {
-        self.contents.iter()
-    }
-
-    pub fn contains(&self, item: &RucksackItem) -> bool {
-        self.contents.contains(item)
+    pub fn items(&self) -> &RucksackItemSet {
+        &self.contents
     }
 }
 
@@ -1,4 +1,4 @@
-#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd)]
 pub struct RucksackItem {
     value: char,
 }
@@ -14,6 +14,23 @@ impl RucksackItem {
             panic!("Invalid char: {}", self.value)
         }
     }
+
+    /// Returns whether `input` is a letter, and so has a priority.
+    pub fn is_valid(input: char) -> bool {
+        input.is_ascii_alphabetic()
+    }
+
+    /// The inverse of [RucksackItem::get_priority].
+    pub fn from_priority(priority: u8) -> Self {
+        let value = match priority {
+            1..=26 => priority + 96,
+            27..=52 => priority + 38,
+            _a => panic!("Invalid priority: {}", _a),
+        };
+        RucksackItem {
+            value: value as char,
+        }
+    }
 }
 
 impl From<char> for RucksackItem {
@@ -22,6 +39,12 @@ impl From<char> for RucksackItem {
     }
 }
 
+impl From<RucksackItem> for char {
+    fn from(input: RucksackItem) -> Self {
+        input.value
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::rucksack_item::RucksackItem;
@@ -40,4 +63,12 @@ mod tests {
         let z_upper = RucksackItem::from('Z');
         assert_eq!(z_upper.get_priority(), 52);
     }
+
+    #[test]
+    fn converts_from_priority() {
+        for c in ('a'..='z').chain('A'..='Z') {
+            let item = RucksackItem::from(c);
+            assert_eq!(RucksackItem::from_priority(item.get_priority()), item);
+        }
+    }
 }
@@ -0,0 +1,134 @@
+use crate::rucksack_item::RucksackItem;
+use std::fmt::{Display, Formatter, Write};
+use std::iter::from_fn;
+
+/// A set of rucksack items, stored as a 52-bit mask where bit `n` is the item with priority `n + 1`.
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq, Hash)]
+pub struct RucksackItemSet {
+    bits: u64,
+}
+
+impl RucksackItemSet {
+    pub fn new() -> Self {
+        RucksackItemSet::default()
+    }
+
+    pub fn insert(&mut self, item: RucksackItem) {
+        self.bits |= RucksackItemSet::mask(&item);
+    }
+
+    pub fn len(&self) -> usize {
+        self.bits.count_ones() as usize
+    }
+
+    /// Returns the only item in the set, or `None` if the set doesn't hold exactly one item.
+    pub fn single(&self) -> Option<RucksackItem> {
+        match self.len() {
+            1 => self.iter().next(),
+            _ => None,
+        }
+    }
+
+    pub fn union(&self, other: &RucksackItemSet) -> RucksackItemSet {
+        RucksackItemSet {
+            bits: self.bits | other.bits,
+        }
+    }
+
+    pub fn intersection(&self, other: &RucksackItemSet) -> RucksackItemSet {
+        RucksackItemSet {
+            bits: self.bits & other.bits,
+        }
+    }
+
+    /// Returns the items present in every one of `sets`, or an empty set if there are none.
+    pub fn intersection_of<'a, I>(sets: I) -> RucksackItemSet
+    where
+        I: IntoIterator<Item = &'a RucksackItemSet>,
+    {
+        sets.into_iter()
+            .copied()
+            .reduce(|acc, val| acc.intersection(&val))
+            .unwrap_or_default()
+    }
+
+    /// Iterates the items in the set in ascending priority order.
+    pub fn iter(&self) -> impl Iterator<Item = RucksackItem> {
+        let mut remaining = self.bits;
+        from_fn(move || {
+            if remaining == 0 {
+                return None;
+            }
+            let index = remaining.trailing_zeros();
+            remaining &= remaining - 1;
+            Some(RucksackItem::from_priority(index as u8 + 1))
+        })
+    }
+
+    fn mask(item: &RucksackItem) -> u64 {
+        1 << (item.get_priority() - 1)
+    }
+}
+
+impl FromIterator<RucksackItem> for RucksackItemSet {
+    fn from_iter<T: IntoIterator<Item = RucksackItem>>(iter: T) -> Self {
+        let mut set = RucksackItemSet::new();
+        for item in iter {
+            set.insert(item);
+        }
+        set
+    }
+}
+
+impl Display for RucksackItemSet {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for item in self.iter() {
+            f.write_char(item.into())?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::rucksack_item::RucksackItem;
+    use crate::rucksack_item_set::RucksackItemSet;
+
+    fn set_of(input: &str) -> RucksackItemSet {
+        input.chars().map(RucksackItem::from).collect()
+    }
+
+    #[test]
+    fn single() {
+        assert_eq!(set_of("zz").single(), Some(RucksackItem::from('z')));
+        assert_eq!(set_of("za").single(), None);
+        assert_eq!(set_of("").single(), None);
+    }
+
+    #[test]
+    fn ignores_duplicates() {
+        let set = set_of("aaaa");
+        assert_eq!(set.len(), 1);
+    }
+
+    #[test]
+    fn iterates_in_priority_order() {
+        let items: String = set_of("ZbAa").iter().map(char::from).collect();
+        assert_eq!(items, "abAZ");
+    }
+
+    #[test]
+    fn intersection_of_many() {
+        let sets = [set_of("abcX"), set_of("bcXY"), set_of("cXZ")];
+        let shared: String = RucksackItemSet::intersection_of(&sets)
+            .iter()
+            .map(char::from)
+            .collect();
+        assert_eq!(shared, "cX");
+    }
+
+    #[test]
+    fn intersection_of_none_is_empty() {
+        assert_eq!(RucksackItemSet::intersection_of(&[]).len(), 0);
+    }
+}
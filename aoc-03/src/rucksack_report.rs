@@ -0,0 +1,264 @@
+use crate::elf_group::ElfGroup;
+use crate::rucksack::Rucksack;
+use crate::rucksack_item::RucksackItem;
+use crate::rucksack_item_set::RucksackItemSet;
+use std::fmt::{Display, Formatter};
+
+/// A problem found in the puzzle input, or in how it was asked to be grouped. Line numbers start
+/// at 1.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum RucksackIssue {
+    InvalidGroupSize {
+        size: usize,
+    },
+    OddLength {
+        line: usize,
+        length: usize,
+    },
+    InvalidCharacter {
+        line: usize,
+        column: usize,
+        value: char,
+    },
+    MisplacedItemCount {
+        line: usize,
+        items: RucksackItemSet,
+    },
+    IncompleteGroup {
+        line: usize,
+        size: usize,
+    },
+    BadgeCount {
+        line: usize,
+        badges: RucksackItemSet,
+    },
+}
+
+impl Display for RucksackIssue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RucksackIssue::InvalidGroupSize { size } => {
+                write!(f, "groups must have at least 1 rucksack, not {}", size)
+            }
+            RucksackIssue::OddLength { line, length } => {
+                write!(f, "line {}: odd length {}", line, length)
+            }
+            RucksackIssue::InvalidCharacter {
+                line,
+                column,
+                value,
+            } => write!(
+                f,
+                "line {}: invalid character {:?} at column {}",
+                line, value, column
+            ),
+            RucksackIssue::MisplacedItemCount { line, items } => write!(
+                f,
+                "line {}: expected 1 misplaced item, found {} ({})",
+                line,
+                items.len(),
+                items
+            ),
+            RucksackIssue::IncompleteGroup { line, size } => {
+                write!(f, "line {}: group has only {} rucksacks", line, size)
+            }
+            RucksackIssue::BadgeCount { line, badges } => write!(
+                f,
+                "line {}: expected 1 badge for group, found {} ({})",
+                line,
+                badges.len(),
+                badges
+            ),
+        }
+    }
+}
+
+/// Validates the puzzle input, and computes the answers only if no issues were found.
+pub struct RucksackReport {
+    issues: Vec<RucksackIssue>,
+    groups: Vec<ElfGroup>,
+}
+
+impl RucksackReport {
+    pub fn new(lines: &[&str], group_size: usize) -> Self {
+        let mut issues = Vec::new();
+        let mut groups = Vec::new();
+        if group_size == 0 {
+            issues.push(RucksackIssue::InvalidGroupSize { size: group_size });
+            return RucksackReport { issues, groups };
+        }
+        for (chunk_index, chunk) in lines.chunks(group_size).enumerate() {
+            let first_line = chunk_index * group_size + 1;
+            let mut chunk_valid = true;
+            for (offset, line) in chunk.iter().enumerate() {
+                let line_issues = RucksackReport::validate_line(first_line + offset, line);
+                chunk_valid &= line_issues.is_empty();
+                issues.extend(line_issues);
+            }
+            if chunk.len() != group_size {
+                chunk_valid = false;
+                issues.push(RucksackIssue::IncompleteGroup {
+                    line: first_line,
+                    size: chunk.len(),
+                });
+            }
+            if !chunk_valid {
+                continue;
+            }
+
+            let group = ElfGroup::from(chunk);
+            let badges = group.find_badges();
+            if badges.single().is_none() {
+                issues.push(RucksackIssue::BadgeCount {
+                    line: first_line,
+                    badges,
+                });
+            }
+            groups.push(group);
+        }
+
+        RucksackReport { issues, groups }
+    }
+
+    pub fn issues(&self) -> &[RucksackIssue] {
+        &self.issues
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    pub fn sum_misplaced_items(&self) -> Option<u64> {
+        if !self.is_valid() {
+            return None;
+        }
+        self.groups.iter().map(ElfGroup::sum_misplaced_items).sum()
+    }
+
+    pub fn sum_badges(&self) -> Option<u64> {
+        if !self.is_valid() {
+            return None;
+        }
+        self.groups
+            .iter()
+            .map(|g| g.find_badge().map(|b| b.get_priority() as u64))
+            .sum()
+    }
+
+    fn validate_line(line: usize, input: &str) -> Vec<RucksackIssue> {
+        let mut issues: Vec<RucksackIssue> = input
+            .chars()
+            .enumerate()
+            .filter(|(_, c)| !RucksackItem::is_valid(*c))
+            .map(|(i, value)| RucksackIssue::InvalidCharacter {
+                line,
+                column: i + 1,
+                value,
+            })
+            .collect();
+        let length = input.chars().count();
+        if !length.is_multiple_of(2) {
+            issues.push(RucksackIssue::OddLength { line, length });
+        }
+        if issues.is_empty() {
+            let items = Rucksack::from(input).find_misplaced_items();
+            if items.single().is_none() {
+                issues.push(RucksackIssue::MisplacedItemCount { line, items });
+            }
+        }
+        issues
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::rucksack_item::RucksackItem;
+    use crate::rucksack_item_set::RucksackItemSet;
+    use crate::rucksack_report::{RucksackIssue, RucksackReport};
+
+    const EXAMPLE: [&str; 6] = [
+        "vJrwpWtwJgWrhcsFMMfFFhFp",
+        "jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL",
+        "PmmdzqPrVvPwwTWBwg",
+        "wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn",
+        "ttgJtRGJQctTZtZT",
+        "CrZsJsPPZsGzwwsLwLmpwMDw",
+    ];
+
+    fn set_of(input: &str) -> RucksackItemSet {
+        input.chars().map(RucksackItem::from).collect()
+    }
+
+    #[test]
+    fn valid_input() {
+        let report = RucksackReport::new(&EXAMPLE, 3);
+        assert!(report.is_valid());
+        assert_eq!(report.sum_misplaced_items(), Some(157));
+        assert_eq!(report.sum_badges(), Some(70));
+    }
+
+    #[test]
+    fn reports_line_issues() {
+        let lines = ["abca", "abcab", "ab1b", "abcd"];
+        let report = RucksackReport::new(&lines, 4);
+        assert_eq!(
+            report.issues(),
+            [
+                RucksackIssue::OddLength { line: 2, length: 5 },
+                RucksackIssue::InvalidCharacter {
+                    line: 3,
+                    column: 3,
+                    value: '1'
+                },
+                RucksackIssue::MisplacedItemCount {
+                    line: 4,
+                    items: set_of("")
+                },
+            ]
+        );
+        assert_eq!(report.sum_misplaced_items(), None);
+        assert_eq!(report.sum_badges(), None);
+    }
+
+    #[test]
+    fn reports_multiple_misplaced_items() {
+        let report = RucksackReport::new(&["abab"], 1);
+        assert_eq!(
+            report.issues(),
+            [RucksackIssue::MisplacedItemCount {
+                line: 1,
+                items: set_of("ab")
+            }]
+        );
+    }
+
+    #[test]
+    fn rejects_empty_groups() {
+        let report = RucksackReport::new(&EXAMPLE, 0);
+        assert_eq!(
+            report.issues(),
+            [RucksackIssue::InvalidGroupSize { size: 0 }]
+        );
+        assert_eq!(report.sum_badges(), None);
+    }
+
+    #[test]
+    fn reports_group_issues() {
+        let lines = ["abca", "abab", "bcdb", "adea", "afga", "fhif", "fjkf"];
+        let report = RucksackReport::new(&lines, 3);
+        assert_eq!(
+            report.issues(),
+            [
+                RucksackIssue::MisplacedItemCount {
+                    line: 2,
+                    items: set_of("ab")
+                },
+                RucksackIssue::BadgeCount {
+                    line: 4,
+                    badges: set_of("")
+                },
+                RucksackIssue::IncompleteGroup { line: 7, size: 1 },
+            ]
+        );
+    }
+}
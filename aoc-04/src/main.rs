@@ -1,7 +1,9 @@
 mod worker_assignment;
 mod worker_pair;
+mod worker_schedule;
 
 use crate::worker_pair::WorkerPair;
+use crate::worker_schedule::WorkerSchedule;
 use aoc_core::puzzle_input::PuzzleInput;
 
 fn main() {
@@ -18,4 +20,24 @@ fn main() {
         "Pairs in which one assignment partially overlaps another: {}",
         partial_overlaps
     );
+
+    let schedule = WorkerSchedule::from(pairs.as_slice());
+    if let Some(bounds) = schedule.bounds() {
+        println!(
+            "Sections {:?} covered: {}, gaps: {:?}",
+            bounds,
+            schedule.coverage_size(&bounds),
+            schedule.gaps(&bounds)
+        );
+    }
+    println!(
+        "Sections covered by more than 10 workers: {:?}",
+        schedule.covered_by_more_than(10)
+    );
+    println!(
+        "Redundant assignments: {} of {}, minimal cover: {}",
+        schedule.redundant().len(),
+        schedule.assignments().len(),
+        schedule.minimal_cover().len()
+    );
 }
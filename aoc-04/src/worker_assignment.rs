@@ -2,10 +2,17 @@ use aoc_core::includes::Includes;
 use aoc_core::overlaps::Overlaps;
 use std::ops::RangeInclusive;
 
+#[derive(Clone)]
 pub struct WorkerAssignment {
     range: RangeInclusive<u32>,
 }
 
+impl WorkerAssignment {
+    pub fn range(&self) -> &RangeInclusive<u32> {
+        &self.range
+    }
+}
+
 impl From<&str> for WorkerAssignment {
     fn from(input: &str) -> Self {
         let mut split = input.split('-');
@@ -15,6 +15,10 @@ impl WorkerPair {
     pub fn does_overlap(&self) -> bool {
         self.a.overlaps(&self.b)
     }
+
+    pub fn iter(&self) -> impl Iterator<Item = &WorkerAssignment> {
+        [&self.a, &self.b].into_iter()
+    }
 }
 
 impl From<&str> for WorkerPair {
@@ -0,0 +1,246 @@
+use crate::worker_assignment::WorkerAssignment;
+use crate::worker_pair::WorkerPair;
+use aoc_core::includes::Includes;
+use aoc_core::overlaps::Overlaps;
+use std::collections::BTreeMap;
+use std::ops::RangeInclusive;
+
+/// Every assignment across all pairs, for answering questions about the schedule as a whole.
+pub struct WorkerSchedule {
+    assignments: Vec<WorkerAssignment>,
+}
+
+impl WorkerSchedule {
+    pub fn assignments(&self) -> &[WorkerAssignment] {
+        &self.assignments
+    }
+
+    /// The smallest range that includes every assignment, or `None` if there are no assignments.
+    pub fn bounds(&self) -> Option<RangeInclusive<u32>> {
+        let start = self.assignments.iter().map(|a| *a.range().start()).min()?;
+        let end = self.assignments.iter().map(|a| *a.range().end()).max()?;
+        Some(start..=end)
+    }
+
+    /// Returns the disjoint ranges of sections within `within` that at least one worker is assigned to.
+    pub fn coverage(&self, within: &RangeInclusive<u32>) -> Vec<RangeInclusive<u32>> {
+        clip(self.covered_by_more_than(0), within)
+    }
+
+    /// Counts the sections within `within` that at least one worker is assigned to.
+    pub fn coverage_size(&self, within: &RangeInclusive<u32>) -> u32 {
+        self.coverage(within)
+            .iter()
+            .map(|r| r.end() - r.start() + 1)
+            .sum()
+    }
+
+    /// Returns the disjoint ranges of sections within `within` that no worker is assigned to.
+    pub fn gaps(&self, within: &RangeInclusive<u32>) -> Vec<RangeInclusive<u32>> {
+        let mut gaps = Vec::new();
+        let mut next = *within.start() as u64;
+        for covered in self.coverage(within) {
+            if (*covered.start() as u64) > next {
+                gaps.push(next as u32..=covered.start() - 1);
+            }
+            next = *covered.end() as u64 + 1;
+        }
+        if next <= *within.end() as u64 {
+            gaps.push(next as u32..=*within.end());
+        }
+        gaps
+    }
+
+    /// Returns the disjoint ranges of sections that more than `k` workers are assigned to.
+    pub fn covered_by_more_than(&self, k: usize) -> Vec<RangeInclusive<u32>> {
+        let mut merged: Vec<RangeInclusive<u32>> = Vec::new();
+        for (range, depth) in self.depth_segments() {
+            if depth <= k {
+                continue;
+            }
+            match merged.last_mut() {
+                Some(last) if *last.end() as u64 + 1 == *range.start() as u64 => {
+                    *last = *last.start()..=*range.end();
+                }
+                _ => merged.push(range),
+            }
+        }
+        merged
+    }
+
+    /// Returns the indices of assignments whose sections are all covered by other assignments.
+    pub fn redundant(&self) -> Vec<usize> {
+        let shared = self.covered_by_more_than(1);
+        self.assignments
+            .iter()
+            .enumerate()
+            .filter(|(_, a)| shared.iter().any(|r| r.includes(a.range())))
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// Returns the indices of a smallest set of assignments that covers the same sections as the
+    /// whole schedule.
+    pub fn minimal_cover(&self) -> Vec<usize> {
+        let start_of = |i: usize| *self.assignments[i].range().start() as u64;
+        let end_of = |i: usize| *self.assignments[i].range().end() as u64;
+        let mut order: Vec<usize> = (0..self.assignments.len())
+            .filter(|i| !self.assignments[*i].range().is_empty())
+            .collect();
+        order.sort_by_key(|i| start_of(*i));
+
+        let mut chosen = Vec::new();
+        let mut reached: Option<u64> = None;
+        let mut next = 0;
+        while next < order.len() {
+            // The first section not yet covered, either just past what was reached or the start of
+            // the next disconnected block of assignments.
+            let frontier = match reached {
+                Some(r) if start_of(order[next]) <= r + 1 => r + 1,
+                _ => start_of(order[next]),
+            };
+            let mut best: Option<usize> = None;
+            while next < order.len() && start_of(order[next]) <= frontier {
+                if best.is_none_or(|b| end_of(order[next]) > end_of(b)) {
+                    best = Some(order[next]);
+                }
+                next += 1;
+            }
+            if let Some(b) = best.filter(|b| end_of(*b) >= frontier) {
+                reached = Some(end_of(b));
+                chosen.push(b);
+            }
+        }
+        chosen.sort();
+        chosen
+    }
+
+    /// Splits the schedule into consecutive, non-empty ranges, each with the number of workers
+    /// assigned to every section in it.
+    fn depth_segments(&self) -> Vec<(RangeInclusive<u32>, usize)> {
+        let mut deltas: BTreeMap<u64, isize> = BTreeMap::new();
+        for assignment in &self.assignments {
+            let range = assignment.range();
+            if range.is_empty() {
+                continue;
+            }
+            *deltas.entry(*range.start() as u64).or_default() += 1;
+            *deltas.entry(*range.end() as u64 + 1).or_default() -= 1;
+        }
+
+        let mut segments = Vec::new();
+        let mut depth = 0isize;
+        let mut points = deltas.into_iter().peekable();
+        while let Some((point, delta)) = points.next() {
+            depth += delta;
+            if let Some((next, _)) = points.peek() {
+                if depth > 0 {
+                    segments.push((point as u32..=(next - 1) as u32, depth as usize));
+                }
+            }
+        }
+        segments
+    }
+}
+
+fn clip(
+    ranges: Vec<RangeInclusive<u32>>,
+    within: &RangeInclusive<u32>,
+) -> Vec<RangeInclusive<u32>> {
+    ranges
+        .into_iter()
+        .filter(|r| r.overlaps(within))
+        .map(|r| *r.start().max(within.start())..=*r.end().min(within.end()))
+        .collect()
+}
+
+impl From<&[WorkerPair]> for WorkerSchedule {
+    fn from(input: &[WorkerPair]) -> Self {
+        let assignments = input.iter().flat_map(WorkerPair::iter).cloned().collect();
+        WorkerSchedule { assignments }
+    }
+}
+
+impl FromIterator<WorkerAssignment> for WorkerSchedule {
+    fn from_iter<T: IntoIterator<Item = WorkerAssignment>>(iter: T) -> Self {
+        WorkerSchedule {
+            assignments: iter.into_iter().collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::worker_assignment::WorkerAssignment;
+    use crate::worker_pair::WorkerPair;
+    use crate::worker_schedule::WorkerSchedule;
+
+    fn schedule_of(input: &[&str]) -> WorkerSchedule {
+        input.iter().map(|s| WorkerAssignment::from(*s)).collect()
+    }
+
+    #[test]
+    fn from_pairs() {
+        let pairs: Vec<WorkerPair> = ["2-4,6-8", "2-3,4-5"]
+            .iter()
+            .map(|s| WorkerPair::from(*s))
+            .collect();
+        let schedule = WorkerSchedule::from(pairs.as_slice());
+        assert_eq!(schedule.assignments().len(), 4);
+        assert_eq!(schedule.bounds(), Some(2..=8));
+    }
+
+    #[test]
+    fn coverage_and_gaps() {
+        let schedule = schedule_of(&["2-4", "6-8", "5-5", "12-15"]);
+        assert_eq!(schedule.coverage(&(0..=20)), vec![2..=8, 12..=15]);
+        assert_eq!(schedule.coverage(&(3..=13)), vec![3..=8, 12..=13]);
+        assert_eq!(schedule.coverage_size(&(0..=20)), 11);
+        assert_eq!(schedule.gaps(&(0..=20)), vec![0..=1, 9..=11, 16..=20]);
+        assert_eq!(schedule.gaps(&(2..=15)), vec![9..=11]);
+        assert_eq!(schedule.gaps(&(2..=8)), vec![]);
+    }
+
+    #[test]
+    fn gaps_at_upper_limit() {
+        let schedule = schedule_of(&["0-10"]);
+        assert_eq!(schedule.gaps(&(5..=u32::MAX)), vec![11..=u32::MAX]);
+        let schedule = schedule_of(&["10-4294967295"]);
+        assert_eq!(schedule.gaps(&(0..=u32::MAX)), vec![0..=9]);
+    }
+
+    #[test]
+    fn covered_by_more_than() {
+        let schedule = schedule_of(&["1-10", "5-15", "8-9", "20-30", "25-25"]);
+        assert_eq!(schedule.covered_by_more_than(1), vec![5..=10, 25..=25]);
+        assert_eq!(schedule.covered_by_more_than(2), vec![8..=9]);
+        assert_eq!(schedule.covered_by_more_than(3), vec![]);
+    }
+
+    #[test]
+    fn redundant() {
+        let schedule = schedule_of(&["1-5", "4-8", "2-6", "10-12", "10-12", "13-14"]);
+        assert_eq!(schedule.redundant(), vec![2, 3, 4]);
+    }
+
+    #[test]
+    fn minimal_cover() {
+        let schedule = schedule_of(&["1-5", "4-8", "2-6", "6-9", "10-12", "10-11", "20-21"]);
+        let cover = schedule.minimal_cover();
+        assert_eq!(cover, vec![0, 3, 4, 6]);
+
+        let covered: WorkerSchedule = cover
+            .iter()
+            .map(|i| schedule.assignments()[*i].clone())
+            .collect();
+        assert_eq!(covered.coverage(&(0..=30)), schedule.coverage(&(0..=30)));
+    }
+
+    #[test]
+    fn empty_schedule() {
+        let schedule = schedule_of(&[]);
+        assert_eq!(schedule.bounds(), None);
+        assert_eq!(schedule.gaps(&(1..=3)), vec![1..=3]);
+        assert_eq!(schedule.minimal_cover(), Vec::<usize>::new());
+    }
+}
@@ -0,0 +1,38 @@
+use crate::crane_model::CraneModel;
+use crate::crate_item::CrateItem;
+
+/// A crane that lifts whole stacks like the CrateMover 9001, except that every other batch (the
+/// second, fourth, and so on) is flipped upside down on the way. Each batch costs 1.
+pub struct AlternatingCrane;
+
+impl CraneModel for AlternatingCrane {
+    fn relocate(&self, index: usize, mut taken: Vec<CrateItem>) -> (Vec<CrateItem>, usize) {
+        if index % 2 == 1 {
+            taken.reverse();
+        }
+        (taken, 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::alternating_crane::AlternatingCrane;
+    use crate::crane_model::tests::items;
+    use crate::crane_model::CraneModel;
+
+    #[test]
+    fn reverses_every_other_batch() {
+        assert_eq!(
+            AlternatingCrane.relocate(0, items("A B C")).0,
+            items("A B C")
+        );
+        assert_eq!(
+            AlternatingCrane.relocate(1, items("A B C")).0,
+            items("C B A")
+        );
+        assert_eq!(
+            AlternatingCrane.relocate(2, items("A B C")).0,
+            items("A B C")
+        );
+    }
+}
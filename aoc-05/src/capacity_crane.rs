@@ -0,0 +1,45 @@
+use crate::crane_model::CraneModel;
+use crate::crate_item::CrateItem;
+
+/// A crane that lifts whole stacks like the CrateMover 9001, but can only lift `capacity` crates at
+/// once, so larger moves are split into several lifts. Each lift costs 1.
+pub struct CapacityCrane {
+    pub capacity: usize,
+}
+
+impl CraneModel for CapacityCrane {
+    fn relocate(&self, _index: usize, mut taken: Vec<CrateItem>) -> (Vec<CrateItem>, usize) {
+        let capacity = self.capacity.max(1);
+        let mut placed = Vec::with_capacity(taken.len());
+        let mut lifts = 0;
+        while !taken.is_empty() {
+            let lift = taken.split_off(taken.len().saturating_sub(capacity));
+            placed.extend(lift);
+            lifts += 1;
+        }
+        (placed, lifts)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::capacity_crane::CapacityCrane;
+    use crate::crane_model::tests::items;
+    use crate::crane_model::CraneModel;
+
+    #[test]
+    fn splits_large_moves() {
+        let crane = CapacityCrane { capacity: 2 };
+        let (placed, cost) = crane.relocate(0, items("A B C D E"));
+        assert_eq!(placed, items("D E B C A"));
+        assert_eq!(cost, 3);
+    }
+
+    #[test]
+    fn small_moves_are_one_lift() {
+        let crane = CapacityCrane { capacity: 5 };
+        let (placed, cost) = crane.relocate(0, items("A B C"));
+        assert_eq!(placed, items("A B C"));
+        assert_eq!(cost, 1);
+    }
+}
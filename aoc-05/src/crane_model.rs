@@ -0,0 +1,34 @@
+use crate::crate_crane::CraneType;
+use crate::crate_item::CrateItem;
+
+/// Describes how a crane moves crates from one stack to another.
+pub trait CraneModel {
+    /// Given the crates taken from the source stack for the instruction at `index`, bottom-most
+    /// first, returns them in the order they end up on the destination stack, bottom-most first,
+    /// along with the cost of the move. Every crate taken must be placed exactly once, or the
+    /// move fails.
+    fn relocate(&self, index: usize, taken: Vec<CrateItem>) -> (Vec<CrateItem>, usize);
+}
+
+impl CraneModel for CraneType {
+    fn relocate(&self, _index: usize, mut taken: Vec<CrateItem>) -> (Vec<CrateItem>, usize) {
+        match self {
+            CraneType::Single => {
+                let cost = taken.len();
+                taken.reverse();
+                (taken, cost)
+            }
+            CraneType::Stack => (taken, 1),
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use crate::crate_item::CrateItem;
+
+    /// The crates labelled in `input`, separated by spaces, bottom-most first.
+    pub fn items(input: &str) -> Vec<CrateItem> {
+        input.split(' ').map(CrateItem::from).collect()
+    }
+}
@@ -1,51 +1,167 @@
 use std::fmt::Display;
 use std::fmt::Formatter;
+use std::iter::once;
 use std::ops::Add;
+use std::ops::Range;
 use std::str::Lines;
 
-use crate::crate_instruction::CrateInstruction;
+use aoc_core::overlaps::Overlaps;
+
+use crate::crane_model::CraneModel;
+use crate::crate_crane_error::CrateCraneError;
+use crate::crate_instruction::{CrateInstruction, CrateInstructionError};
 use crate::crate_item::CrateItem;
+use crate::crate_log_entry::CrateLogEntry;
+use crate::crate_parse_error::CrateParseError;
 use crate::crate_stack::CrateStack;
 
+/// The two original crane models: the CrateMover 9000, which moves one crate at a time, and the
+/// CrateMover 9001, which moves a whole stack at once.
+#[derive(Copy, Clone)]
 pub enum CraneType {
     Single,
     Stack,
 }
 
+#[derive(Debug)]
 pub struct CrateCrane {
-    stacks: [CrateStack; 9],
+    stacks: Vec<CrateStack>,
     instructions: Vec<CrateInstruction>,
+    history: Vec<CrateLogEntry>,
 }
 
 impl CrateCrane {
-    pub fn process_instructions(&mut self, crane_type: CraneType) {
-        let process = match crane_type {
-            CraneType::Single => CrateCrane::process_single,
-            CraneType::Stack => CrateCrane::process_stack,
-        };
-        while !self.instructions.is_empty() {
-            let inst = self.instructions.pop().unwrap();
-            process(self, &inst);
+    pub fn new(stacks: Vec<CrateStack>, instructions: Vec<CrateInstruction>) -> Self {
+        CrateCrane {
+            stacks,
+            instructions,
+            history: vec![],
         }
     }
 
-    fn process_single(&mut self, inst: &CrateInstruction) {
-        for _ in 0..inst.count {
-            let item = self.stacks[inst.from].pop().unwrap();
-            self.stacks[inst.to].push(item);
+    /// Writes the current stacks and the remaining instructions in the puzzle input format, without
+    /// trailing spaces, such that parsing the result gives back an equal crane. With no stacks,
+    /// the drawing is left out entirely, so the output starts with a blank line.
+    pub fn serialize(&self) -> String {
+        let mut output = String::new();
+        for line in self.to_string().lines() {
+            output.push_str(line.trim_end());
+            output.push('\n');
+        }
+        output.push('\n');
+        for inst in &self.instructions[self.position()..] {
+            output.push_str(&format!("{}\n", inst));
         }
+        output
     }
 
-    fn process_stack(&mut self, inst: &CrateInstruction) {
-        let mut grabbed: Vec<CrateItem> = vec![];
-        for _ in 0..inst.count {
-            let item = self.stacks[inst.from].pop().unwrap();
-            grabbed.push(item);
+    /// Processes every remaining instruction, panicking if any of them is invalid.
+    pub fn process_instructions(&mut self, model: &dyn CraneModel) {
+        if let Err(e) = self.try_process_instructions(model) {
+            panic!("{}", e);
         }
-        grabbed.reverse();
-        for item in grabbed {
-            self.stacks[inst.to].push(item);
+    }
+
+    /// Processes the remaining instructions in order, validating each against the current stacks
+    /// before it runs. Stops at the first invalid instruction, leaving it and every instruction
+    /// after it unprocessed.
+    pub fn try_process_instructions(
+        &mut self,
+        model: &dyn CraneModel,
+    ) -> Result<(), CrateCraneError> {
+        while self.step(model)? {}
+        Ok(())
+    }
+
+    /// Checks every remaining instruction without changing the crane, returning the index of each
+    /// invalid one along with why. Invalid instructions are treated as skipped when checking the
+    /// ones after them.
+    pub fn validate_instructions(&self) -> Vec<(usize, CrateInstructionError)> {
+        let mut stacks = self.stacks.clone();
+        let mut invalid = vec![];
+        for (index, inst) in self.instructions.iter().enumerate().skip(self.position()) {
+            match inst.validate(&stacks) {
+                Ok(()) => {
+                    let taken = stacks[inst.from].take(inst.count);
+                    stacks[inst.to].extend(taken);
+                }
+                Err(e) => invalid.push((index, e)),
+            }
         }
+        invalid
+    }
+
+    /// The number of instructions that have been processed, which is also the index of the next
+    /// instruction to process.
+    pub fn position(&self) -> usize {
+        self.history.len()
+    }
+
+    /// Every processed instruction, in the order they were processed.
+    pub fn history(&self) -> &[CrateLogEntry] {
+        &self.history
+    }
+
+    /// The total cost of every processed instruction, as reported by the crane models that
+    /// processed them.
+    pub fn total_cost(&self) -> usize {
+        self.history.iter().map(|e| e.cost).sum()
+    }
+
+    /// Processes the next instruction. Returns `false` if there were none left. Fails without
+    /// changing the stacks if the instruction is invalid, or if `model` doesn't place every crate
+    /// it took, since undoing the move relies on that.
+    pub fn step(&mut self, model: &dyn CraneModel) -> Result<bool, CrateCraneError> {
+        let index = self.position();
+        let Some(inst) = self.instructions.get(index) else {
+            return Ok(false);
+        };
+        if let Err(error) = inst.validate(&self.stacks) {
+            return Err(CrateCraneError {
+                index,
+                error,
+                stacks: self.stacks.clone(),
+            });
+        }
+
+        let taken = self.stacks[inst.from].take(inst.count);
+        let (placed, cost) = model.relocate(index, taken.clone());
+        if placed.len() != taken.len() {
+            let error = CrateInstructionError::CratesMiscounted {
+                taken: taken.len(),
+                placed: placed.len(),
+            };
+            self.stacks[inst.from].extend(taken);
+            return Err(CrateCraneError {
+                index,
+                error,
+                stacks: self.stacks.clone(),
+            });
+        }
+        self.stacks[inst.to].extend(placed);
+        self.history.push(CrateLogEntry { index, taken, cost });
+        Ok(true)
+    }
+
+    /// Reverts the last processed instruction. Returns `false` if there was nothing to revert.
+    pub fn undo(&mut self) -> bool {
+        let Some(entry) = self.history.pop() else {
+            return false;
+        };
+        let inst = &self.instructions[entry.index];
+        self.stacks[inst.to].take(entry.taken.len());
+        self.stacks[inst.from].extend(entry.taken);
+        true
+    }
+
+    /// Steps forward or backward until exactly `position` instructions have been processed, or
+    /// every instruction has been if `position` is past the end.
+    pub fn seek(&mut self, position: usize, model: &dyn CraneModel) -> Result<(), CrateCraneError> {
+        while self.position() > position {
+            self.undo();
+        }
+        while self.position() < position && self.step(model)? {}
+        Ok(())
     }
 
     pub fn top_code(&self) -> String {
@@ -53,70 +169,450 @@ impl CrateCrane {
             .iter()
             .map(|x| {
                 x.peek()
-                    .map(|c| format_args!("{}", c).to_string())
+                    .map(CrateItem::to_string)
                     .unwrap_or(String::from(" "))
             })
             .fold(String::new(), |acc, val| acc.add(&val))
     }
 }
 
+/// Cranes are equal if their stacks and remaining instructions are, regardless of history.
+impl PartialEq for CrateCrane {
+    fn eq(&self, other: &Self) -> bool {
+        self.stacks == other.stacks
+            && self.instructions[self.position()..] == other.instructions[other.position()..]
+    }
+}
+
 impl Display for CrateCrane {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        let max_size = self.stacks.iter().map(CrateStack::size).max().unwrap();
-        for line_num in (0..max_size).rev() {
-            for stack in &self.stacks {
-                let item = stack.get(line_num);
-                if item.is_none() {
-                    f.write_str("   ")?;
-                } else {
-                    f.write_fmt(format_args!("[{}]", item.unwrap()))?;
-                }
+        write_drawing(f, &self.stacks)
+    }
+}
+
+/// Draws `stacks` in the same layout as the puzzle input.
+pub fn write_drawing(f: &mut Formatter<'_>, stacks: &[CrateStack]) -> std::fmt::Result {
+    let max_size = stacks.iter().map(CrateStack::size).max().unwrap_or(0);
+    let width = stacks
+        .iter()
+        .flat_map(CrateStack::iter)
+        .map(CrateItem::width)
+        .max()
+        .unwrap_or(1);
+    for line_num in (0..max_size).rev() {
+        for (i, stack) in stacks.iter().enumerate() {
+            if i > 0 {
                 f.write_str(" ")?;
             }
-            f.write_str("\n")?;
+            match stack.get(line_num) {
+                Some(item) => f.write_fmt(format_args!("[{:^w$}]", item, w = width))?,
+                None => f.write_fmt(format_args!("{:w$}", "", w = width + 2))?,
+            }
         }
-        for i in 0..self.stacks.len() {
-            f.write_fmt(format_args!(" {}  ", i + 1))?;
+        f.write_str("\n")?;
+    }
+    for i in 0..stacks.len() {
+        if i > 0 {
+            f.write_str(" ")?;
         }
-        Ok(())
+        f.write_fmt(format_args!("{:^w$}", i + 1, w = width + 2))?;
     }
+    Ok(())
 }
 
-impl From<Lines<'_>> for CrateCrane {
-    fn from(mut input: Lines<'_>) -> Self {
-        let mut stack_items: [Vec<CrateItem>; 9] = [
-            vec![],
-            vec![],
-            vec![],
-            vec![],
-            vec![],
-            vec![],
-            vec![],
-            vec![],
-            vec![],
-        ];
-        let stack_lines = input.by_ref().take_while(|l| l.contains('['));
-        for line in stack_lines {
-            let mut chars = line.chars();
-            for i in 0..9usize {
-                let char = chars.by_ref().skip(1).next().unwrap();
-                if char != ' ' {
-                    stack_items[i].push(CrateItem::from(char));
-                }
-                chars.by_ref().take(2).count();
+impl TryFrom<Lines<'_>> for CrateCrane {
+    type Error = CrateParseError;
+
+    fn try_from(input: Lines<'_>) -> Result<Self, Self::Error> {
+        let mut input = input.enumerate();
+        let mut drawing: Vec<&str> = vec![];
+        let labels = loop {
+            let (_, line) = input.next().ok_or(CrateParseError::MissingLabels)?;
+            // A blank line before any drawing means there are no stacks.
+            if drawing.is_empty() && line.trim().is_empty() {
+                break vec![];
+            }
+            if is_label_line(line) {
+                break find_labels(line);
+            }
+            drawing.push(line);
+        };
+
+        let mut stack_items: Vec<Vec<CrateItem>> = vec![vec![]; labels.len()];
+        for line in drawing.iter().rev() {
+            for (span, label) in find_crates(line) {
+                stack_items[find_stack(&labels, &span)].push(CrateItem::from(label.as_str()));
             }
         }
-        let stacks = stack_items.map(|mut items| {
-            items.reverse();
-            CrateStack::from(items)
-        });
+        let stacks = stack_items.into_iter().map(CrateStack::from).collect();
 
-        input.next();
-        let instructions: Vec<CrateInstruction> = input.rev().map(CrateInstruction::from).collect();
+        let instructions = input
+            .filter(|(_, l)| !l.trim().is_empty())
+            .map(|(i, l)| {
+                CrateInstruction::try_from(l).map_err(|_| CrateParseError::InvalidInstruction {
+                    line: i + 1,
+                    text: l.to_string(),
+                })
+            })
+            .collect::<Result<Vec<CrateInstruction>, CrateParseError>>()?;
 
-        CrateCrane {
-            instructions,
-            stacks,
+        Ok(CrateCrane::new(stacks, instructions))
+    }
+}
+
+/// The line under the drawing that numbers each stack.
+fn is_label_line(line: &str) -> bool {
+    !line.trim().is_empty()
+        && line
+            .chars()
+            .all(|c| c.is_whitespace() || c.is_ascii_digit())
+}
+
+/// Returns the span of characters taken up by each stack's label.
+fn find_labels(line: &str) -> Vec<Range<usize>> {
+    let mut labels = vec![];
+    let mut start = None;
+    for (i, c) in line.chars().chain(once(' ')).enumerate() {
+        match (c.is_whitespace(), start) {
+            (false, None) => start = Some(i),
+            (true, Some(s)) => {
+                labels.push(s..i);
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    labels
+}
+
+/// Returns the span of characters taken up by each `[...]` crate in a line of the drawing, along
+/// with the text between the brackets.
+fn find_crates(line: &str) -> Vec<(Range<usize>, String)> {
+    let mut crates = vec![];
+    let mut current: Option<(usize, String)> = None;
+    for (i, c) in line.chars().enumerate() {
+        match (c, current.as_mut()) {
+            ('[', None) => current = Some((i, String::new())),
+            (']', Some(_)) => {
+                let (start, label) = current.take().unwrap();
+                crates.push((start..i + 1, label));
+            }
+            (_, Some((_, label))) => label.push(c),
+            _ => {}
+        }
+    }
+    crates
+}
+
+/// Picks the stack whose label overlaps the crate, or failing that, whose label is nearest to it.
+fn find_stack(labels: &[Range<usize>], span: &Range<usize>) -> usize {
+    labels
+        .iter()
+        .enumerate()
+        .min_by_key(|(_, l)| {
+            let distance = (l.start + l.end).abs_diff(span.start + span.end);
+            (!l.overlaps(span), distance)
+        })
+        .map(|(i, _)| i)
+        .expect("Drawing has no stacks!")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::crane_model::CraneModel;
+    use crate::crate_crane::{CraneType, CrateCrane};
+    use crate::crate_instruction::CrateInstructionError;
+    use crate::crate_item::CrateItem;
+    use crate::crate_parse_error::CrateParseError;
+    use crate::crate_puzzle_generator::CratePuzzleGenerator;
+
+    const EXAMPLE: &str = "    [D]    
+[N] [C]    
+[Z] [M] [P]
+ 1   2   3 
+
+move 1 from 2 to 1
+move 3 from 1 to 3
+move 2 from 2 to 1
+move 1 from 1 to 2
+";
+
+    #[test]
+    fn process_single() {
+        let mut crane = CrateCrane::try_from(EXAMPLE.lines()).unwrap();
+        crane.process_instructions(&CraneType::Single);
+        assert_eq!(crane.top_code(), "CMZ");
+    }
+
+    #[test]
+    fn process_stack() {
+        let mut crane = CrateCrane::try_from(EXAMPLE.lines()).unwrap();
+        crane.process_instructions(&CraneType::Stack);
+        assert_eq!(crane.top_code(), "MCD");
+    }
+
+    #[test]
+    fn parses_trimmed_lines() {
+        let trimmed: String = EXAMPLE
+            .lines()
+            .map(|l| l.trim_end().to_owned() + "\n")
+            .collect();
+        let crane = CrateCrane::try_from(trimmed.lines()).unwrap();
+        assert_eq!(
+            crane.to_string(),
+            CrateCrane::try_from(EXAMPLE.lines()).unwrap().to_string()
+        );
+        assert_eq!(crane.top_code(), "NDP");
+    }
+
+    #[test]
+    fn parses_any_number_of_stacks() {
+        let input = "                                        [K]
+[A] [B] [C] [D] [E] [F] [G] [H] [I] [J] [L]
+ 1   2   3   4   5   6   7   8   9   10  11
+
+move 1 from 11 to 1
+";
+        let mut crane = CrateCrane::try_from(input.lines()).unwrap();
+        assert_eq!(crane.top_code(), "ABCDEFGHIJK");
+        crane.process_instructions(&CraneType::Single);
+        assert_eq!(crane.top_code(), "KBCDEFGHIJL");
+    }
+
+    #[test]
+    fn parses_multi_char_labels() {
+        let input = "[AB]
+[CD] [EF]
+ 1    2
+";
+        let crane = CrateCrane::try_from(input.lines()).unwrap();
+        assert_eq!(crane.top_code(), "ABEF");
+        assert_eq!(crane.to_string(), "[AB]     \n[CD] [EF]\n 1    2  ");
+    }
+
+    #[test]
+    fn stops_at_invalid_instruction() {
+        let input = EXAMPLE.replace("move 2 from 2 to 1", "move 3 from 2 to 1");
+        let mut crane = CrateCrane::try_from(input.lines()).unwrap();
+        let error = crane
+            .try_process_instructions(&CraneType::Single)
+            .unwrap_err();
+        assert_eq!(error.index, 2);
+        assert_eq!(
+            error.error,
+            CrateInstructionError::NotEnoughCrates {
+                stack: 1,
+                requested: 3,
+                available: 2
+            }
+        );
+        assert_eq!(error.stacks[0].size(), 0);
+        assert_eq!(error.stacks[2].size(), 4);
+        assert_eq!(crane.top_code(), " CZ");
+    }
+
+    #[test]
+    fn validates_all_instructions() {
+        let input = EXAMPLE
+            .replace("move 1 from 2 to 1", "move 1 from 4 to 1")
+            .replace("move 2 from 2 to 1", "move 4 from 2 to 1");
+        let crane = CrateCrane::try_from(input.lines()).unwrap();
+        assert_eq!(
+            crane.validate_instructions(),
+            vec![
+                (0, CrateInstructionError::StackOutOfRange { stack: 3 }),
+                (
+                    1,
+                    CrateInstructionError::NotEnoughCrates {
+                        stack: 0,
+                        requested: 3,
+                        available: 2
+                    }
+                ),
+                (
+                    2,
+                    CrateInstructionError::NotEnoughCrates {
+                        stack: 1,
+                        requested: 4,
+                        available: 3
+                    }
+                ),
+            ]
+        );
+        assert_eq!(crane.top_code(), "NDP");
+    }
+
+    #[test]
+    fn steps_and_undoes() {
+        let mut crane = CrateCrane::try_from(EXAMPLE.lines()).unwrap();
+        let initial = crane.to_string();
+        assert!(crane.step(&CraneType::Single).unwrap());
+        assert_eq!(crane.position(), 1);
+        assert_eq!(crane.top_code(), "DCP");
+        let after_first = crane.to_string();
+
+        assert!(crane.step(&CraneType::Single).unwrap());
+        assert_eq!(crane.top_code(), " CZ");
+        assert!(crane.undo());
+        assert_eq!(crane.to_string(), after_first);
+        assert!(crane.undo());
+        assert_eq!(crane.to_string(), initial);
+        assert!(!crane.undo());
+        assert_eq!(crane.position(), 0);
+    }
+
+    #[test]
+    fn seeks_in_both_directions() {
+        let mut crane = CrateCrane::try_from(EXAMPLE.lines()).unwrap();
+        crane.seek(4, &CraneType::Stack).unwrap();
+        assert_eq!(crane.top_code(), "MCD");
+        assert_eq!(crane.total_cost(), 4);
+        assert_eq!(crane.history().len(), 4);
+        assert_eq!(crane.history()[1].taken.len(), 3);
+
+        crane.seek(0, &CraneType::Stack).unwrap();
+        assert_eq!(crane.top_code(), "NDP");
+        crane.seek(10, &CraneType::Single).unwrap();
+        assert_eq!(crane.position(), 4);
+        assert_eq!(crane.top_code(), "CMZ");
+        assert_eq!(crane.total_cost(), 7);
+
+        crane.seek(2, &CraneType::Single).unwrap();
+        assert_eq!(crane.top_code(), " CZ");
+        assert!(crane.step(&CraneType::Single).unwrap());
+        crane.process_instructions(&CraneType::Single);
+        assert_eq!(crane.top_code(), "CMZ");
+    }
+
+    #[test]
+    fn rejects_models_that_lose_crates() {
+        struct LosingCrane;
+        impl CraneModel for LosingCrane {
+            fn relocate(
+                &self,
+                _index: usize,
+                mut taken: Vec<CrateItem>,
+            ) -> (Vec<CrateItem>, usize) {
+                taken.pop();
+                (taken, 1)
+            }
+        }
+
+        let mut crane = CrateCrane::try_from(EXAMPLE.lines()).unwrap();
+        let before = crane.to_string();
+        let error = crane.step(&LosingCrane).unwrap_err();
+        assert_eq!(
+            error.error,
+            CrateInstructionError::CratesMiscounted {
+                taken: 1,
+                placed: 0
+            }
+        );
+        assert_eq!(crane.to_string(), before);
+        assert_eq!(crane.position(), 0);
+    }
+
+    #[test]
+    fn reports_parse_errors() {
+        assert_eq!(
+            CrateCrane::try_from("[A]\n\nmove 1 from 1 to 2".lines()).err(),
+            Some(CrateParseError::MissingLabels)
+        );
+        assert_eq!(
+            CrateCrane::try_from(" 1   2\n\nmove 1 from 1 to 2\nmove 1 to 2\n".lines()).err(),
+            Some(CrateParseError::InvalidInstruction {
+                line: 4,
+                text: "move 1 to 2".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn serializes_in_puzzle_format() {
+        let crane = CrateCrane::try_from(EXAMPLE.lines()).unwrap();
+        let expected = concat!(
+            "    [D]\n",
+            "[N] [C]\n",
+            "[Z] [M] [P]\n",
+            " 1   2   3\n",
+            "\n",
+            "move 1 from 2 to 1\n",
+            "move 3 from 1 to 3\n",
+            "move 2 from 2 to 1\n",
+            "move 1 from 1 to 2\n",
+        );
+        assert_eq!(crane.serialize(), expected);
+        assert_eq!(
+            CrateCrane::try_from(crane.serialize().lines()).unwrap(),
+            crane
+        );
+    }
+
+    #[test]
+    fn serializes_without_trailing_spaces() {
+        let input = concat!(
+            "    [Q]\n",
+            "[T] [W]     [D]\n",
+            "[F] [Q] [R] [S]\n",
+            " 1   2   3   4\n",
+            "\n",
+            "move 1 from 3 to 2\n",
+            "move 2 from 4 to 1\n",
+        );
+        let crane = CrateCrane::try_from(input.lines()).unwrap();
+        assert_eq!(crane.serialize(), input);
+    }
+
+    #[test]
+    fn serializes_without_stacks() {
+        let crane = CrateCrane::new(vec![], vec![]);
+        assert_eq!(crane.serialize(), "\n");
+        assert_eq!(CrateCrane::try_from("\n".lines()).unwrap(), crane);
+
+        let crane = CrateCrane::try_from("\nmove 1 from 1 to 2\n".lines()).unwrap();
+        assert_eq!(crane.serialize(), "\nmove 1 from 1 to 2\n");
+        assert_eq!(crane.validate_instructions().len(), 1);
+    }
+
+    #[test]
+    fn serializes_remaining_instructions() {
+        let mut crane = CrateCrane::try_from(EXAMPLE.lines()).unwrap();
+        crane.seek(2, &CraneType::Stack).unwrap();
+        let expected = concat!(
+            "        [D]\n",
+            "        [N]\n",
+            "    [C] [Z]\n",
+            "    [M] [P]\n",
+            " 1   2   3\n",
+            "\n",
+            "move 2 from 2 to 1\n",
+            "move 1 from 1 to 2\n",
+        );
+        assert_eq!(crane.serialize(), expected);
+        let mut parsed = CrateCrane::try_from(expected.lines()).unwrap();
+        assert_eq!(parsed, crane);
+        parsed.process_instructions(&CraneType::Stack);
+        crane.process_instructions(&CraneType::Stack);
+        assert_eq!(parsed, crane);
+    }
+
+    #[test]
+    fn round_trips_generated_puzzles() {
+        for seed in 0..200 {
+            let mut generator = CratePuzzleGenerator::new(seed);
+            let mut crane = generator.generate(seed as usize % 12, 40, 60);
+            let serialized = crane.serialize();
+            assert_eq!(
+                CrateCrane::try_from(serialized.lines()).unwrap(),
+                crane,
+                "{}",
+                serialized
+            );
+            assert!(crane.validate_instructions().is_empty());
+
+            crane.process_instructions(&CraneType::Single);
+            crane.seek(0, &CraneType::Single).unwrap();
+            assert_eq!(crane.serialize(), serialized);
         }
     }
 }
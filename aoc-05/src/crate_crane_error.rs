@@ -0,0 +1,19 @@
+use crate::crate_crane::write_drawing;
+use crate::crate_instruction::CrateInstructionError;
+use crate::crate_stack::CrateStack;
+use std::fmt::{Display, Formatter};
+
+/// An instruction that couldn't be carried out, along with the stacks as they were when it failed.
+#[derive(Debug)]
+pub struct CrateCraneError {
+    pub index: usize,
+    pub error: CrateInstructionError,
+    pub stacks: Vec<CrateStack>,
+}
+
+impl Display for CrateCraneError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Instruction {} failed: {}", self.index + 1, self.error)?;
+        write_drawing(f, &self.stacks)
+    }
+}
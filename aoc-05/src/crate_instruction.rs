@@ -1,19 +1,104 @@
+use crate::crate_stack::CrateStack;
+use std::fmt::{Display, Formatter};
+
+/// Why an instruction can't be carried out. Stacks are numbered from 0.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CrateInstructionError {
+    StackOutOfRange {
+        stack: usize,
+    },
+    NotEnoughCrates {
+        stack: usize,
+        requested: usize,
+        available: usize,
+    },
+    /// The crane model placed a different number of crates than it was given.
+    CratesMiscounted {
+        taken: usize,
+        placed: usize,
+    },
+}
+
+impl Display for CrateInstructionError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CrateInstructionError::StackOutOfRange { stack } => {
+                write!(f, "stack {} does not exist", stack.wrapping_add(1))
+            }
+            CrateInstructionError::NotEnoughCrates {
+                stack,
+                requested,
+                available,
+            } => write!(
+                f,
+                "stack {} has {} crates, but {} were requested",
+                stack + 1,
+                available,
+                requested
+            ),
+            CrateInstructionError::CratesMiscounted { taken, placed } => {
+                write!(f, "the crane took {} crates, but placed {}", taken, placed)
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct CrateInstruction {
     pub count: usize,
     pub from: usize,
     pub to: usize,
 }
 
-impl From<&str> for CrateInstruction {
-    fn from(input: &str) -> Self {
-        let mut values = input
-            .split(' ')
-            .map(str::parse::<usize>)
-            .filter(Result::is_ok)
-            .map(Result::unwrap);
-        let count = values.next().unwrap();
-        let from = values.next().unwrap() - 1;
-        let to = values.next().unwrap() - 1;
-        CrateInstruction { count, from, to }
+impl CrateInstruction {
+    /// Checks that this instruction can be carried out on `stacks`.
+    pub fn validate(&self, stacks: &[CrateStack]) -> Result<(), CrateInstructionError> {
+        for stack in [self.from, self.to] {
+            if stack >= stacks.len() {
+                return Err(CrateInstructionError::StackOutOfRange { stack });
+            }
+        }
+        let available = stacks[self.from].size();
+        if self.count > available {
+            return Err(CrateInstructionError::NotEnoughCrates {
+                stack: self.from,
+                requested: self.count,
+                available,
+            });
+        }
+        Ok(())
+    }
+}
+
+impl Display for CrateInstruction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "move {} from {} to {}",
+            self.count,
+            self.from.wrapping_add(1),
+            self.to.wrapping_add(1)
+        )
+    }
+}
+
+/// Reads the three numbers of a `move 1 from 2 to 3` line, ignoring the words around them.
+impl TryFrom<&str> for CrateInstruction {
+    type Error = ();
+
+    fn try_from(input: &str) -> Result<Self, Self::Error> {
+        let values: Vec<usize> = input
+            .split_whitespace()
+            .filter_map(|s| s.parse().ok())
+            .collect();
+        let [count, from, to] = values[..] else {
+            return Err(());
+        };
+        // Stack 0 wraps around, and so is caught as out of range by `validate`.
+        Ok(CrateInstruction {
+            count,
+            from: from.wrapping_sub(1),
+            to: to.wrapping_sub(1),
+        })
     }
 }
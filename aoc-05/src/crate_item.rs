@@ -1,19 +1,28 @@
-use std::fmt::{Display, Formatter, Write};
+use std::fmt::{Display, Formatter};
 
-#[derive(Copy, Clone, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct CrateItem {
-    contents: char,
+    contents: String,
 }
 
-impl From<char> for CrateItem {
-    fn from(input: char) -> Self {
-        CrateItem { contents: input }
+impl CrateItem {
+    /// The number of characters in the crate's label, not including the brackets.
+    pub fn width(&self) -> usize {
+        self.contents.chars().count()
+    }
+}
+
+impl From<&str> for CrateItem {
+    fn from(input: &str) -> Self {
+        CrateItem {
+            contents: String::from(input),
+        }
     }
 }
 
 impl Display for CrateItem {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        f.write_char(self.contents)?;
+        f.write_str(&self.contents)?;
         Ok(())
     }
 }
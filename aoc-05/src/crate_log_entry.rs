@@ -0,0 +1,12 @@
+use crate::crate_item::CrateItem;
+
+/// A record of a processed instruction, with enough detail to undo it.
+#[derive(Clone, Debug)]
+pub struct CrateLogEntry {
+    /// The index of the instruction that was processed.
+    pub index: usize,
+    /// The crates that were taken from the source stack, bottom-most first.
+    pub taken: Vec<CrateItem>,
+    /// The cost of the move, as reported by the crane model.
+    pub cost: usize,
+}
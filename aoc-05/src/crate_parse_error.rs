@@ -0,0 +1,19 @@
+use std::fmt::{Display, Formatter};
+
+/// A problem found while reading the puzzle input. Line numbers start at 1.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CrateParseError {
+    MissingLabels,
+    InvalidInstruction { line: usize, text: String },
+}
+
+impl Display for CrateParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CrateParseError::MissingLabels => write!(f, "drawing has no line of stack labels"),
+            CrateParseError::InvalidInstruction { line, text } => {
+                write!(f, "line {}: invalid instruction {:?}", line, text)
+            }
+        }
+    }
+}
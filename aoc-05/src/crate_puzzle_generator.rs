@@ -0,0 +1,64 @@
+use crate::crate_crane::CrateCrane;
+use crate::crate_instruction::CrateInstruction;
+use crate::crate_item::CrateItem;
+use crate::crate_stack::CrateStack;
+
+/// Generates random, valid puzzles from a seed, so the same seed always gives the same puzzle.
+pub struct CratePuzzleGenerator {
+    state: u64,
+}
+
+impl CratePuzzleGenerator {
+    pub fn new(seed: u64) -> Self {
+        CratePuzzleGenerator { state: seed }
+    }
+
+    /// Generates a puzzle with `stack_count` stacks, `crate_count` crates spread between them, and
+    /// `instruction_count` instructions that are each valid when processed in order. With no stacks,
+    /// the puzzle is empty.
+    pub fn generate(
+        &mut self,
+        stack_count: usize,
+        crate_count: usize,
+        instruction_count: usize,
+    ) -> CrateCrane {
+        // With no stacks there is nowhere to put crates, and so nothing to move.
+        if stack_count == 0 {
+            return CrateCrane::new(vec![], vec![]);
+        }
+        let mut stack_items: Vec<Vec<CrateItem>> = vec![vec![]; stack_count];
+        for _ in 0..crate_count {
+            let label = (b'A' + self.next_below(26) as u8) as char;
+            let stack = self.next_below(stack_count);
+            stack_items[stack].push(CrateItem::from(label.to_string().as_str()));
+        }
+
+        let mut sizes: Vec<usize> = stack_items.iter().map(Vec::len).collect();
+        let mut instructions = vec![];
+        for _ in 0..instruction_count {
+            let non_empty: Vec<usize> = (0..stack_count).filter(|s| sizes[*s] > 0).collect();
+            if non_empty.is_empty() {
+                break;
+            }
+            let from = non_empty[self.next_below(non_empty.len())];
+            let to = self.next_below(stack_count);
+            let count = 1 + self.next_below(sizes[from]);
+            sizes[from] -= count;
+            sizes[to] += count;
+            instructions.push(CrateInstruction { count, from, to });
+        }
+
+        let stacks = stack_items.into_iter().map(CrateStack::from).collect();
+        CrateCrane::new(stacks, instructions)
+    }
+
+    /// Returns a number in `0..bound`, using splitmix64.
+    fn next_below(&mut self, bound: usize) -> usize {
+        self.state = self.state.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^= z >> 31;
+        (z % bound as u64) as usize
+    }
+}
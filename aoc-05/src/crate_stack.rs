@@ -1,5 +1,6 @@
 use crate::crate_item::CrateItem;
 
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct CrateStack {
     crates: Vec<CrateItem>,
 }
@@ -17,12 +18,18 @@ impl CrateStack {
         self.crates.last()
     }
 
-    pub fn pop(&mut self) -> Option<CrateItem> {
-        self.crates.pop()
+    /// Removes the top `count` crates and returns them, bottom-most first.
+    pub fn take(&mut self, count: usize) -> Vec<CrateItem> {
+        self.crates.split_off(self.crates.len() - count)
     }
 
-    pub fn push(&mut self, item: CrateItem) {
-        self.crates.push(item);
+    /// Places `items` on top of the stack, bottom-most first.
+    pub fn extend(&mut self, items: Vec<CrateItem>) {
+        self.crates.extend(items);
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &CrateItem> {
+        self.crates.iter()
     }
 }
 
@@ -1,25 +1,84 @@
+use crate::alternating_crane::AlternatingCrane;
+use crate::capacity_crane::CapacityCrane;
+use crate::crane_model::CraneModel;
 use crate::crate_crane::{CraneType, CrateCrane};
+use crate::timed_crane::TimedCrane;
 use aoc_core::puzzle_input::PuzzleInput;
 
+mod alternating_crane;
+mod capacity_crane;
+mod crane_model;
 mod crate_crane;
+mod crate_crane_error;
 mod crate_instruction;
 mod crate_item;
+mod crate_log_entry;
+mod crate_parse_error;
+#[cfg(test)]
+mod crate_puzzle_generator;
 mod crate_stack;
+mod timed_crane;
 
 fn main() {
     let input = PuzzleInput::default();
-    let mut crane = CrateCrane::from(input.to_lines());
+    let mut crane = match CrateCrane::try_from(input.to_lines()) {
+        Ok(crane) => crane,
+        Err(e) => {
+            println!("Invalid puzzle input: {}", e);
+            return;
+        }
+    };
     println!("Initial crate arrangement:");
     println!("{}", crane);
 
-    crane.process_instructions(CraneType::Single);
+    let invalid = crane.validate_instructions();
+    if !invalid.is_empty() {
+        println!("Found {} invalid instructions:", invalid.len());
+        for (index, error) in invalid {
+            println!("  {}: {}", index + 1, error);
+        }
+        return;
+    }
+
+    if let Err(e) = crane.try_process_instructions(&CraneType::Single) {
+        println!("{}", e);
+        println!("Remaining puzzle:");
+        println!("{}", crane.serialize());
+        return;
+    }
     println!("Final crate arrangement (single):");
     println!("{}", crane);
     println!("{}", crane.top_code());
+    let moved: usize = crane.history().iter().map(|e| e.taken.len()).sum();
+    println!("Moved {} crates", moved);
 
-    let mut crane = CrateCrane::from(input.to_lines());
-    crane.process_instructions(CraneType::Stack);
+    crane.seek(0, &CraneType::Stack).unwrap();
+    crane.process_instructions(&CraneType::Stack);
     println!("Final crate arrangement (stack):");
     println!("{}", crane);
     println!("{}", crane.top_code());
+
+    let models: [(&str, &dyn CraneModel); 5] = [
+        ("single", &CraneType::Single),
+        ("stack", &CraneType::Stack),
+        ("capacity 3", &CapacityCrane { capacity: 3 }),
+        ("alternating", &AlternatingCrane),
+        (
+            "timed",
+            &TimedCrane {
+                setup_time: 5,
+                time_per_crate: 2,
+            },
+        ),
+    ];
+    for (name, model) in models {
+        crane.seek(0, model).unwrap();
+        crane.process_instructions(model);
+        println!(
+            "{:>12}: {} (cost {})",
+            name,
+            crane.top_code(),
+            crane.total_cost()
+        );
+    }
 }
@@ -0,0 +1,35 @@
+use crate::crane_model::CraneModel;
+use crate::crate_item::CrateItem;
+
+/// A crane that lifts whole stacks like the CrateMover 9001, where the cost of each move is the
+/// time it takes: `setup_time` to get into position, plus `time_per_crate` for each crate lifted.
+pub struct TimedCrane {
+    pub setup_time: usize,
+    pub time_per_crate: usize,
+}
+
+impl CraneModel for TimedCrane {
+    fn relocate(&self, _index: usize, taken: Vec<CrateItem>) -> (Vec<CrateItem>, usize) {
+        let cost = self.setup_time + self.time_per_crate * taken.len();
+        (taken, cost)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::crane_model::tests::items;
+    use crate::crane_model::CraneModel;
+    use crate::timed_crane::TimedCrane;
+
+    #[test]
+    fn costs_setup_and_crates() {
+        let crane = TimedCrane {
+            setup_time: 5,
+            time_per_crate: 2,
+        };
+        let (placed, cost) = crane.relocate(0, items("A B C"));
+        assert_eq!(placed, items("A B C"));
+        assert_eq!(cost, 11);
+        assert_eq!(crane.relocate(1, items("A")).1, 7);
+    }
+}
@@ -1,27 +1,91 @@
-use std::collections::HashSet;
+use crate::marker_window::MarkerWindow;
+use std::hash::Hash;
 
-pub struct DataStream {
-    value: Vec<char>,
+pub struct DataStream<T> {
+    value: Vec<T>,
 }
 
-impl DataStream {
-    pub fn find_marker(&self, size: usize) -> usize {
+impl<T> DataStream<T>
+where
+    T: Eq + Hash + Copy,
+{
+    /// Returns the number of symbols read before the first marker of `size` distinct symbols is
+    /// complete, or `None` if the stream has no such marker.
+    pub fn find_marker(&self, size: usize) -> Option<usize> {
+        self.markers(size).next()
+    }
+
+    /// Iterates every position at which the previous `size` symbols are all different, in order.
+    pub fn markers(&self, size: usize) -> impl Iterator<Item = usize> + '_ {
+        let mut window = MarkerWindow::new(size);
         self.value
-            .windows(size)
+            .iter()
             .enumerate()
-            .find(|(_, win)| {
-                let set: HashSet<&char> = HashSet::from_iter(win.iter());
-                set.len() == size
-            })
-            .map(|(r, _)| r + size)
-            .unwrap()
+            .filter(move |(_, s)| window.push(**s))
+            .map(|(i, _)| i + 1)
     }
 }
 
-impl From<&str> for DataStream {
+impl From<&str> for DataStream<char> {
     fn from(input: &str) -> Self {
         DataStream {
             value: input.chars().collect(),
         }
     }
 }
+
+impl<T> From<&[T]> for DataStream<T>
+where
+    T: Copy,
+{
+    fn from(input: &[T]) -> Self {
+        DataStream {
+            value: input.to_vec(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::data_stream::DataStream;
+
+    const EXAMPLES: [(&str, usize, usize); 5] = [
+        ("mjqjpqmgbljsphdztnvjfqwrcgsmlb", 7, 19),
+        ("bvwbjplbgvbhsrlpgdmjqwftvncz", 5, 23),
+        ("nppdvjthqldpwncqszvftbrmjlhg", 6, 23),
+        ("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", 10, 29),
+        ("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", 11, 26),
+    ];
+
+    #[test]
+    fn finds_markers_in_chars() {
+        for (input, packet, message) in EXAMPLES {
+            let stream = DataStream::from(input);
+            assert_eq!(stream.find_marker(4), Some(packet));
+            assert_eq!(stream.find_marker(14), Some(message));
+        }
+    }
+
+    #[test]
+    fn finds_markers_in_bytes() {
+        for (input, packet, message) in EXAMPLES {
+            let stream = DataStream::from(input.as_bytes());
+            assert_eq!(stream.find_marker(4), Some(packet));
+            assert_eq!(stream.find_marker(14), Some(message));
+        }
+    }
+
+    #[test]
+    fn no_marker() {
+        let stream = DataStream::from("abcabcabc");
+        assert_eq!(stream.find_marker(4), None);
+        assert_eq!(DataStream::from("ab").find_marker(3), None);
+    }
+
+    #[test]
+    fn iterates_every_marker() {
+        let stream = DataStream::from("abcabcaab");
+        let markers: Vec<usize> = stream.markers(3).collect();
+        assert_eq!(markers, [3, 4, 5, 6, 7]);
+    }
+}
@@ -1,11 +1,29 @@
 mod data_stream;
+mod marker_detector;
+mod marker_event;
+mod marker_window;
 
 use crate::data_stream::DataStream;
+use crate::marker_detector::MarkerDetector;
 use aoc_core::puzzle_input::PuzzleInput;
 
 fn main() {
     let input = PuzzleInput::default();
     let stream = DataStream::from(input.as_string().as_str());
-    println!("Start-of-packet marker: {}", stream.find_marker(4));
-    println!("Start-of-packet marker: {}", stream.find_marker(14));
+    println!(
+        "Start-of-packet marker: {}",
+        stream.find_marker(4).expect("No start-of-packet marker!")
+    );
+    println!(
+        "Start-of-message marker: {}",
+        stream.find_marker(14).expect("No start-of-message marker!")
+    );
+
+    let mut detector = MarkerDetector::default();
+    detector
+        .scan(input.as_string().as_bytes(), |event| {
+            println!("Streamed {:?} marker: {}", event.kind, event.offset)
+        })
+        .unwrap();
+    println!("Stopped scanning after {} bytes", detector.offset());
 }
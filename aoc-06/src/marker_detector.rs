@@ -0,0 +1,157 @@
+use crate::marker_event::{MarkerEvent, MarkerKind};
+use crate::marker_window::MarkerWindow;
+use std::io::{ErrorKind, Read};
+
+/// Finds the first marker of each kind in a stream of bytes that is fed in a chunk at a time, so
+/// the whole stream never needs to be held in memory.
+pub struct MarkerDetector {
+    offset: usize,
+    pending: Vec<(MarkerKind, MarkerWindow<u8>)>,
+}
+
+impl MarkerDetector {
+    pub fn new(kinds: &[MarkerKind]) -> Self {
+        MarkerDetector {
+            offset: 0,
+            pending: kinds
+                .iter()
+                .map(|k| (*k, MarkerWindow::new(k.size())))
+                .collect(),
+        }
+    }
+
+    /// The number of bytes fed in so far.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Whether every kind of marker has been found.
+    pub fn is_done(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    /// Feeds in the next chunk of the stream, returning any markers that it completed.
+    pub fn feed(&mut self, chunk: &[u8]) -> Vec<MarkerEvent> {
+        let mut events = vec![];
+        for byte in chunk {
+            if self.is_done() {
+                break;
+            }
+            self.offset += 1;
+            let offset = self.offset;
+            self.pending.retain_mut(|(kind, window)| {
+                if window.push(*byte) {
+                    events.push(MarkerEvent {
+                        kind: *kind,
+                        offset,
+                    });
+                    return false;
+                }
+                true
+            });
+        }
+        events
+    }
+
+    /// Reads `reader` until every kind of marker has been found or the stream ends, calling
+    /// `on_event` for each marker as soon as it is found.
+    pub fn scan<R, F>(&mut self, mut reader: R, mut on_event: F) -> std::io::Result<()>
+    where
+        R: Read,
+        F: FnMut(MarkerEvent),
+    {
+        let mut buffer = [0u8; 8192];
+        while !self.is_done() {
+            let read = match reader.read(&mut buffer) {
+                Ok(0) => break,
+                Ok(read) => read,
+                Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+                Err(e) => return Err(e),
+            };
+            self.feed(&buffer[..read])
+                .into_iter()
+                .for_each(&mut on_event);
+        }
+        Ok(())
+    }
+}
+
+impl Default for MarkerDetector {
+    fn default() -> Self {
+        MarkerDetector::new(&[MarkerKind::StartOfPacket, MarkerKind::StartOfMessage])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::marker_detector::MarkerDetector;
+    use crate::marker_event::{MarkerEvent, MarkerKind};
+    use std::io::Read;
+
+    const INPUT: &[u8] = b"mjqjpqmgbljsphdztnvjfqwrcgsmlb";
+
+    fn packet(offset: usize) -> MarkerEvent {
+        MarkerEvent {
+            kind: MarkerKind::StartOfPacket,
+            offset,
+        }
+    }
+
+    fn message(offset: usize) -> MarkerEvent {
+        MarkerEvent {
+            kind: MarkerKind::StartOfMessage,
+            offset,
+        }
+    }
+
+    #[test]
+    fn emits_events_as_chunks_arrive() {
+        let mut detector = MarkerDetector::default();
+        assert_eq!(detector.feed(&INPUT[..5]), []);
+        assert_eq!(detector.feed(&INPUT[5..10]), [packet(7)]);
+        assert!(!detector.is_done());
+        assert_eq!(detector.feed(&INPUT[10..]), [message(19)]);
+        assert!(detector.is_done());
+        assert_eq!(detector.offset(), 19);
+    }
+
+    #[test]
+    fn chunk_size_does_not_matter() {
+        for size in 1..INPUT.len() {
+            let mut detector = MarkerDetector::default();
+            let events: Vec<MarkerEvent> =
+                INPUT.chunks(size).flat_map(|c| detector.feed(c)).collect();
+            assert_eq!(events, [packet(7), message(19)]);
+        }
+    }
+
+    #[test]
+    fn scans_reader() {
+        // Hands out one byte per read, like a slow pipe.
+        struct Trickle<'a>(&'a [u8]);
+        impl Read for Trickle<'_> {
+            fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+                let read = self.0.take(1).read(buf)?;
+                self.0 = &self.0[read..];
+                Ok(read)
+            }
+        }
+
+        let mut events = vec![];
+        let mut detector = MarkerDetector::default();
+        detector.scan(Trickle(INPUT), |e| events.push(e)).unwrap();
+        assert_eq!(events, [packet(7), message(19)]);
+        assert_eq!(detector.offset(), 19);
+    }
+
+    #[test]
+    fn scan_stops_at_end_of_stream() {
+        let mut events = vec![];
+        let mut detector = MarkerDetector::default();
+        detector
+            .scan(&b"abcabcabcd"[..], |e| events.push(e))
+            .unwrap();
+        assert_eq!(events, [packet(10)]);
+        assert!(!detector.is_done());
+    }
+}
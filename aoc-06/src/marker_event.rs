@@ -0,0 +1,22 @@
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum MarkerKind {
+    StartOfPacket,
+    StartOfMessage,
+}
+
+impl MarkerKind {
+    /// The number of distinct symbols in a row that make up this kind of marker.
+    pub fn size(&self) -> usize {
+        match self {
+            MarkerKind::StartOfPacket => 4,
+            MarkerKind::StartOfMessage => 14,
+        }
+    }
+}
+
+/// The first marker of a kind, found after `offset` bytes of the stream were read.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct MarkerEvent {
+    pub kind: MarkerKind,
+    pub offset: usize,
+}
@@ -0,0 +1,61 @@
+use std::collections::{HashMap, VecDeque};
+use std::hash::Hash;
+
+/// Tracks the last `size` symbols of a stream, along with how many times each symbol appears in
+/// them, so checking whether they're all different takes constant time per symbol.
+pub struct MarkerWindow<T> {
+    size: usize,
+    recent: VecDeque<T>,
+    counts: HashMap<T, usize>,
+}
+
+impl<T> MarkerWindow<T>
+where
+    T: Eq + Hash + Copy,
+{
+    pub fn new(size: usize) -> Self {
+        MarkerWindow {
+            size,
+            recent: VecDeque::with_capacity(size + 1),
+            counts: HashMap::new(),
+        }
+    }
+
+    /// Adds the next symbol of the stream, and returns whether the last `size` symbols are now all
+    /// different.
+    pub fn push(&mut self, symbol: T) -> bool {
+        if self.size == 0 {
+            return true;
+        }
+        if self.recent.len() == self.size {
+            let oldest = self.recent.pop_front().unwrap();
+            let count = self.counts.get_mut(&oldest).unwrap();
+            *count -= 1;
+            if *count == 0 {
+                self.counts.remove(&oldest);
+            }
+        }
+        self.recent.push_back(symbol);
+        *self.counts.entry(symbol).or_default() += 1;
+        self.recent.len() == self.size && self.counts.len() == self.size
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::marker_window::MarkerWindow;
+
+    #[test]
+    fn detects_distinct_windows() {
+        let mut window = MarkerWindow::new(3);
+        let results: Vec<bool> = "aabcbbd".chars().map(|c| window.push(c)).collect();
+        assert_eq!(results, [false, false, false, true, false, false, false]);
+    }
+
+    #[test]
+    fn zero_size_is_always_distinct() {
+        let mut window = MarkerWindow::new(0);
+        assert!(window.push(b'a'));
+        assert!(window.push(b'a'));
+    }
+}
@@ -1,31 +1,64 @@
+mod system_cleanup;
 mod system_command;
 mod system_directory;
+mod system_error;
+mod system_export;
 mod system_node;
+mod system_path;
 mod system_state;
+mod system_transcript;
+mod system_tree;
 
 use crate::system_command::{ListNode, SystemCommand};
-use crate::system_state::SystemState;
+use crate::system_transcript::TranscriptReport;
+use crate::system_tree::{SystemTree, TreeSort};
 use aoc_core::puzzle_input::PuzzleInput;
 
+const DISK_SIZE: usize = 70_000_000;
+const REQUIRED_FREE: usize = 30_000_000;
+
 fn main() {
     let input = PuzzleInput::default();
 
     let mut commands: Vec<SystemCommand> = Vec::new();
     for line in input.to_lines() {
         if line.starts_with('$') {
-            commands.push(SystemCommand::from(line));
+            commands.push(SystemCommand::try_from(line).unwrap());
         } else {
             let last_command = commands.last_mut().unwrap();
             match last_command {
-                SystemCommand::List(result) => result.push(ListNode::from(line)),
+                SystemCommand::List(result) => result.push(ListNode::try_from(line).unwrap()),
                 _ => panic!("Command has no output!"),
             };
         }
     }
 
-    let mut state = SystemState::default();
-    for command in commands {
-        state.run(command);
+    let report = TranscriptReport::new(commands);
+    if !report.is_consistent() {
+        for issue in report.issues() {
+            println!("Warning: {}", issue);
+        }
+    }
+    if !report.is_complete() {
+        println!("Warning: not every directory was listed, sizes are lower bounds");
+    }
+    let state = report.state;
+
+    let tree = SystemTree {
+        sort: TreeSort::Size,
+        max_depth: Some(1),
+        ..SystemTree::new(&state.root)
+    };
+    println!("{}", tree);
+
+    // Set AOC_07_EXPORT to a path ending in .json or .ndjson to save every file and directory.
+    if let Ok(path) = std::env::var("AOC_07_EXPORT") {
+        let export = match path.ends_with(".json") {
+            true => system_export::to_json(&state.root),
+            false => system_export::to_ndjson(&state.root),
+        };
+        std::fs::write(&path, export).unwrap();
+        println!("Exported filesystem to {}", path);
     }
 
     let mut all_directories = state.root.flat_directories();
@@ -38,8 +71,8 @@ fn main() {
         .sum();
     println!("Sum of all small directories: {}", small_directories);
 
-    let current_space = 70000000 - all_directories.first().unwrap().size();
-    let space_to_free = 30000000 - current_space;
+    let current_space = DISK_SIZE - all_directories.first().unwrap().size();
+    let space_to_free = REQUIRED_FREE - current_space;
     let first_largest = all_directories
         .iter()
         .rev()
@@ -49,4 +82,10 @@ fn main() {
         "Smallest directory that can fix error: {}",
         first_largest.size()
     );
+
+    let plan = state
+        .root
+        .plan_cleanup(DISK_SIZE, REQUIRED_FREE, &[])
+        .unwrap();
+    println!("Smallest cleanup that can fix error:\n{}", plan);
 }
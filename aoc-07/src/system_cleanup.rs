@@ -0,0 +1,313 @@
+use crate::system_directory::SystemDirectory;
+use crate::system_node::SystemNode;
+use crate::system_path;
+use std::collections::HashSet;
+use std::fmt::{Display, Formatter};
+
+/// The files and directories to delete to free enough space on a disk.
+pub struct CleanupPlan {
+    pub disk_size: usize,
+    pub used_before: usize,
+    /// The path and size of each file or directory to delete. None of them is inside another.
+    pub deletions: Vec<(String, usize)>,
+    /// Whether no other plan deletes less. This is `false` if the search for the smallest plan
+    /// gave up, in which case this plan is the smallest it found.
+    pub optimal: bool,
+}
+
+impl CleanupPlan {
+    /// Plans the deletions that leave at least `required_free` space on a disk of `disk_size`,
+    /// deleting as little as possible in total, unless the search gives up first; see
+    /// [`CleanupPlan::optimal`]. Nothing at or under a `protected` path is deleted. Returns `None`
+    /// if there isn't enough unprotected data to free the space.
+    pub fn new(
+        root: &SystemDirectory,
+        disk_size: usize,
+        required_free: usize,
+        protected: &[&str],
+    ) -> Option<Self> {
+        let protected: Vec<Vec<String>> = protected
+            .iter()
+            .map(|p| system_path::resolve(&[], p))
+            .collect();
+        let is_protected = |path: &[String]| protected.iter().any(|p| path.starts_with(p));
+
+        let used_before = root.size();
+        let to_free = (used_before + required_free).saturating_sub(disk_size);
+        let files: Vec<(Vec<String>, usize)> = root
+            .walk()
+            .into_iter()
+            .filter_map(|(path, node)| match node {
+                SystemNode::File(_, size) if *size > 0 && !is_protected(&path) => {
+                    Some((path, *size))
+                }
+                _ => None,
+            })
+            .collect();
+        let sizes: Vec<usize> = files.iter().map(|(_, size)| *size).collect();
+        let (chosen, optimal) = smallest_subset_reaching(&sizes, to_free)?;
+        let selected: HashSet<&[String]> = chosen.iter().map(|i| files[*i].0.as_slice()).collect();
+
+        // Deleting a directory frees the same space as deleting everything in it, so replace
+        // each directory whose files were all selected with the directory itself.
+        let is_deleted = |path: &[String]| selected.contains(path);
+        let mut deletions = Vec::new();
+        collect_deletions(
+            root,
+            &mut vec![],
+            &is_deleted,
+            &is_protected,
+            &mut deletions,
+        );
+        Some(CleanupPlan {
+            disk_size,
+            used_before,
+            deletions,
+            optimal,
+        })
+    }
+
+    pub fn freed(&self) -> usize {
+        self.deletions.iter().map(|(_, size)| size).sum()
+    }
+
+    pub fn used_after(&self) -> usize {
+        self.used_before - self.freed()
+    }
+}
+
+impl Display for CleanupPlan {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (path, size) in &self.deletions {
+            writeln!(f, "delete {} ({})", path, size)?;
+        }
+        write!(
+            f,
+            "used {} -> {} of {}, freeing {}",
+            self.used_before,
+            self.used_after(),
+            self.disk_size,
+            self.freed()
+        )?;
+        if !self.optimal {
+            write!(f, " (a smaller plan may exist)")?;
+        }
+        Ok(())
+    }
+}
+
+fn collect_deletions(
+    dir: &SystemDirectory,
+    path: &mut Vec<String>,
+    is_deleted: &dyn Fn(&[String]) -> bool,
+    is_protected: &dyn Fn(&[String]) -> bool,
+    deletions: &mut Vec<(String, usize)>,
+) {
+    for (name, node) in &dir.contents {
+        path.push(name.clone());
+        if is_covered(node, path, is_deleted, is_protected) {
+            if node.size() > 0 {
+                deletions.push((system_path::format(path), node.size()));
+            }
+        } else if let SystemNode::Directory(inner) = node {
+            collect_deletions(inner, path, is_deleted, is_protected, deletions);
+        }
+        path.pop();
+    }
+}
+
+/// Whether `node` can be deleted as a whole, because everything in it that takes up space is
+/// already being deleted and none of it is protected.
+fn is_covered(
+    node: &SystemNode,
+    path: &mut Vec<String>,
+    is_deleted: &dyn Fn(&[String]) -> bool,
+    is_protected: &dyn Fn(&[String]) -> bool,
+) -> bool {
+    if is_protected(path) {
+        return false;
+    }
+    match node {
+        SystemNode::File(_, size) => *size == 0 || is_deleted(path),
+        SystemNode::Directory(dir) => dir.contents.iter().all(|(name, inner)| {
+            path.push(name.clone());
+            let covered = is_covered(inner, path, is_deleted, is_protected);
+            path.pop();
+            covered
+        }),
+    }
+}
+
+/// How many steps the search in [`smallest_subset_reaching`] may take before it settles for the
+/// best subset found so far.
+const SEARCH_LIMIT: usize = 1_000_000;
+
+/// Returns the indices of the subset of `sizes` with the smallest sum that is at least `target`,
+/// or `None` if even all of them together fall short. Along with it is whether the subset is known
+/// to be the smallest, which it is unless the search gave up after [`SEARCH_LIMIT`] steps.
+fn smallest_subset_reaching(sizes: &[usize], target: usize) -> Option<(Vec<usize>, bool)> {
+    if target == 0 {
+        return Some((Vec::new(), true));
+    }
+    // Try the largest sizes first, so the search reaches the target with few of them and the
+    // remaining sums prune early.
+    let mut order: Vec<usize> = (0..sizes.len()).collect();
+    order.sort_by(|a, b| sizes[*b].cmp(&sizes[*a]));
+    let mut remaining = vec![0; order.len() + 1];
+    for pos in (0..order.len()).rev() {
+        remaining[pos] = remaining[pos + 1] + sizes[order[pos]];
+    }
+    if remaining[0] < target {
+        return None;
+    }
+
+    // Every sum is a multiple of the sizes' greatest common divisor, so no subset can add up to
+    // less than the first such multiple reaching the target.
+    let divisor = sizes.iter().fold(0, |a, b| gcd(a, *b));
+    let mut search = SubsetSearch {
+        sizes,
+        order,
+        remaining,
+        target,
+        lower_bound: target.div_ceil(divisor) * divisor,
+        best: None,
+        steps: 0,
+        gave_up: false,
+    };
+    search.visit(0, 0, &mut Vec::new());
+    let (best_sum, mut chosen) = search.best?;
+    chosen.sort();
+    Some((chosen, best_sum == search.lower_bound || !search.gave_up))
+}
+
+fn gcd(a: usize, b: usize) -> usize {
+    match b {
+        0 => a,
+        _ => gcd(b, a % b),
+    }
+}
+
+/// A depth-first branch-and-bound search, deciding for each size in `order` whether to take it.
+struct SubsetSearch<'a> {
+    sizes: &'a [usize],
+    order: Vec<usize>,
+    /// The sum of the sizes from each position in `order` onwards.
+    remaining: Vec<usize>,
+    target: usize,
+    /// No subset reaching the target adds up to less than this.
+    lower_bound: usize,
+    best: Option<(usize, Vec<usize>)>,
+    steps: usize,
+    /// Whether the search stopped at [`SEARCH_LIMIT`] before ruling out every smaller subset.
+    gave_up: bool,
+}
+
+impl SubsetSearch<'_> {
+    fn visit(&mut self, pos: usize, sum: usize, chosen: &mut Vec<usize>) {
+        if sum >= self.target {
+            if self.best.as_ref().is_none_or(|(b, _)| sum < *b) {
+                self.best = Some((sum, chosen.clone()));
+            }
+            return;
+        }
+        self.steps += 1;
+        let best_sum = self.best.as_ref().map_or(usize::MAX, |(b, _)| *b);
+        if pos == self.order.len()
+            || sum + self.remaining[pos] < self.target
+            || best_sum == self.lower_bound
+        {
+            return;
+        }
+        if self.steps > SEARCH_LIMIT {
+            self.gave_up = true;
+            return;
+        }
+
+        let index = self.order[pos];
+        let size = self.sizes[index];
+        if sum + size < best_sum {
+            chosen.push(index);
+            self.visit(pos + 1, sum + size, chosen);
+            chosen.pop();
+        }
+        // Leaving this size out only differs from leaving out an equal one after it if one of
+        // those is taken instead, which the branch above already covers.
+        let mut next = pos + 1;
+        while next < self.order.len() && self.sizes[self.order[next]] == size {
+            next += 1;
+        }
+        self.visit(next, sum, chosen);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::system_cleanup::{smallest_subset_reaching, CleanupPlan};
+    use crate::system_state::tests::sample;
+
+    #[test]
+    fn finds_smallest_subset() {
+        let smallest = |sizes: &[usize], target| smallest_subset_reaching(sizes, target);
+        assert_eq!(smallest(&[5, 3, 4, 9], 7), Some((vec![1, 2], true)));
+        assert_eq!(smallest(&[5, 3, 4, 9], 13), Some((vec![2, 3], true)));
+        assert_eq!(smallest(&[5, 3], 0), Some((vec![], true)));
+        assert_eq!(smallest(&[5, 3], 9), None);
+        assert_eq!(smallest(&[4, 4, 4, 3, 3], 9), Some((vec![0, 3, 4], true)));
+    }
+
+    #[test]
+    fn finds_subset_for_megabytes() {
+        // Files of every whole number of 4 KB blocks from 1 to 300, so every number of blocks up
+        // to their total can be freed, and the smallest plan frees just enough whole blocks.
+        let sizes: Vec<usize> = (1..=300).map(|blocks| blocks * 4096).collect();
+        for target in [208_000, 3_200_000, 15_000_000, 90_000_000] {
+            let (chosen, optimal) = smallest_subset_reaching(&sizes, target).unwrap();
+            let sum: usize = chosen.iter().map(|i| sizes[*i]).sum();
+            assert_eq!(sum, target.div_ceil(4096) * 4096);
+            assert!(optimal);
+        }
+    }
+
+    #[test]
+    fn plans_smallest_cleanup() {
+        let state = sample();
+        let plan = CleanupPlan::new(&state.root, 70000000, 30000000, &[]).unwrap();
+        assert_eq!(plan.deletions, [("/c.dat".to_string(), 8504156)]);
+        assert_eq!(plan.used_before, 48381165);
+        assert_eq!(plan.used_after(), 39877009);
+        assert!(plan.optimal);
+    }
+
+    #[test]
+    fn respects_protected_paths() {
+        let state = sample();
+        let plan = CleanupPlan::new(&state.root, 70000000, 30000000, &["/c.dat"]).unwrap();
+        assert_eq!(
+            plan.deletions,
+            [
+                ("/d/d.ext".to_string(), 5626152),
+                ("/d/j".to_string(), 4060174)
+            ]
+        );
+        assert!(CleanupPlan::new(&state.root, 70000000, 30000000, &["/"]).is_none());
+    }
+
+    #[test]
+    fn deletes_whole_directories() {
+        let state = sample();
+        let plan = CleanupPlan::new(&state.root, 48381165, 94853, &[]).unwrap();
+        assert_eq!(plan.deletions, [("/a".to_string(), 94853)]);
+        assert_eq!(plan.freed(), 94853);
+
+        let plan = CleanupPlan::new(&state.root, 48381165, 94853, &["/a/e"]).unwrap();
+        assert_eq!(plan.deletions, [("/d/j".to_string(), 4060174)]);
+    }
+
+    #[test]
+    fn nothing_to_delete() {
+        let state = sample();
+        let plan = CleanupPlan::new(&state.root, 70000000, 100, &[]).unwrap();
+        assert!(plan.deletions.is_empty());
+        assert_eq!(plan.used_after(), plan.used_before);
+    }
+}
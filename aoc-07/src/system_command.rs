@@ -1,16 +1,50 @@
+use crate::system_error::SystemError;
+
 pub enum SystemCommand {
     ChangeDirectory(String),
     List(Vec<ListNode>),
+    MakeDirectory(String),
+    Touch(usize, String),
+    Remove(String),
+    Move(String, String),
+    PrintWorkingDirectory,
+    DiskUsage(String),
+    Find(String, SizeFilter),
 }
 
-impl From<&str> for SystemCommand {
-    fn from(input: &str) -> Self {
-        let mut split = input.split(' ').skip(1);
-        match split.next().unwrap() {
-            "cd" => SystemCommand::ChangeDirectory(split.next().unwrap().to_string()),
+impl TryFrom<&str> for SystemCommand {
+    type Error = SystemError;
+
+    fn try_from(input: &str) -> Result<Self, Self::Error> {
+        let mut split = input.split_whitespace().skip(1);
+        let name = split.next().unwrap_or_default();
+        let mut arg = || {
+            split
+                .next()
+                .map(str::to_string)
+                .ok_or_else(|| SystemError::MissingArgument(name.to_string()))
+        };
+        let command = match name {
+            "cd" => SystemCommand::ChangeDirectory(arg()?),
             "ls" => SystemCommand::List(Vec::new()),
-            _a => panic!("Unknown command: {}", _a),
-        }
+            "mkdir" => SystemCommand::MakeDirectory(arg()?),
+            "touch" => SystemCommand::Touch(parse_size(&arg()?)?, arg()?),
+            "rm" => SystemCommand::Remove(arg()?),
+            "mv" => SystemCommand::Move(arg()?, arg()?),
+            "pwd" => SystemCommand::PrintWorkingDirectory,
+            "du" => SystemCommand::DiskUsage(arg().unwrap_or(".".to_string())),
+            "find" => {
+                let mut path = arg()?;
+                if path == "-size" {
+                    path = ".".to_string();
+                } else if arg()? != "-size" {
+                    return Err(SystemError::MissingArgument(name.to_string()));
+                }
+                SystemCommand::Find(path, SizeFilter::try_from(arg()?.as_str())?)
+            }
+            _a => return Err(SystemError::UnknownCommand(_a.to_string())),
+        };
+        Ok(command)
     }
 }
 
@@ -19,15 +53,111 @@ pub enum ListNode {
     File(String, usize),
 }
 
-impl From<&str> for ListNode {
-    fn from(input: &str) -> Self {
-        if input.starts_with("dir") {
-            ListNode::Directory(input.split(' ').skip(1).next().unwrap().to_string())
+impl ListNode {
+    pub fn name(&self) -> &str {
+        match self {
+            ListNode::Directory(name) => name.as_str(),
+            ListNode::File(name, _) => name.as_str(),
+        }
+    }
+}
+
+impl TryFrom<&str> for ListNode {
+    type Error = SystemError;
+
+    fn try_from(input: &str) -> Result<Self, Self::Error> {
+        let mut split = input.split(' ');
+        match (split.next(), split.next()) {
+            (Some("dir"), Some(name)) => Ok(ListNode::Directory(name.to_string())),
+            (Some(size), Some(name)) => Ok(ListNode::File(name.to_string(), parse_size(size)?)),
+            _ => Err(SystemError::InvalidListing(input.to_string())),
+        }
+    }
+}
+
+/// Matches file sizes like `find -size`: `+N` for larger than `N`, `-N` for smaller than `N`, or
+/// `N` for exactly `N`.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum SizeFilter {
+    Above(usize),
+    Below(usize),
+    Exactly(usize),
+}
+
+impl SizeFilter {
+    pub fn matches(&self, size: usize) -> bool {
+        match self {
+            SizeFilter::Above(limit) => size > *limit,
+            SizeFilter::Below(limit) => size < *limit,
+            SizeFilter::Exactly(limit) => size == *limit,
+        }
+    }
+}
+
+impl TryFrom<&str> for SizeFilter {
+    type Error = SystemError;
+
+    fn try_from(input: &str) -> Result<Self, Self::Error> {
+        if let Some(size) = input.strip_prefix('+') {
+            Ok(SizeFilter::Above(parse_size(size)?))
+        } else if let Some(size) = input.strip_prefix('-') {
+            Ok(SizeFilter::Below(parse_size(size)?))
         } else {
-            let mut split = input.split(' ');
-            let size = split.next().map(|x| x.parse().unwrap()).unwrap();
-            let name = split.next().unwrap().to_string();
-            ListNode::File(name, size)
+            Ok(SizeFilter::Exactly(parse_size(input)?))
         }
     }
 }
+
+fn parse_size(input: &str) -> Result<usize, SystemError> {
+    input
+        .parse()
+        .map_err(|_| SystemError::InvalidSize(input.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::system_command::{ListNode, SizeFilter, SystemCommand};
+    use crate::system_error::SystemError;
+
+    #[test]
+    fn parses_commands() {
+        assert!(matches!(
+            SystemCommand::try_from("$ cd /a/b"),
+            Ok(SystemCommand::ChangeDirectory(p)) if p == "/a/b"
+        ));
+        assert!(matches!(
+            SystemCommand::try_from("$ touch 120 f.txt"),
+            Ok(SystemCommand::Touch(120, p)) if p == "f.txt"
+        ));
+        assert!(matches!(
+            SystemCommand::try_from("$ du"),
+            Ok(SystemCommand::DiskUsage(p)) if p == "."
+        ));
+        assert!(matches!(
+            SystemCommand::try_from("$ find -size +100"),
+            Ok(SystemCommand::Find(p, SizeFilter::Above(100))) if p == "."
+        ));
+        assert!(matches!(
+            SystemCommand::try_from("$ find /a -size -5"),
+            Ok(SystemCommand::Find(p, SizeFilter::Below(5))) if p == "/a"
+        ));
+    }
+
+    #[test]
+    fn reports_parse_errors() {
+        assert_eq!(
+            SystemCommand::try_from("$ cat a").err(),
+            Some(SystemError::UnknownCommand("cat".to_string()))
+        );
+        assert_eq!(
+            SystemCommand::try_from("$ mv a").err(),
+            Some(SystemError::MissingArgument("mv".to_string()))
+        );
+        assert_eq!(
+            SystemCommand::try_from("$ touch big f").err(),
+            Some(SystemError::InvalidSize("big".to_string()))
+        );
+        assert!(ListNode::try_from("12x a").is_err());
+        assert!(ListNode::try_from("dir").is_err());
+    }
+}
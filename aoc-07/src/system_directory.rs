@@ -1,9 +1,14 @@
+use crate::system_cleanup::CleanupPlan;
+use crate::system_error::SystemError;
 use crate::system_node::SystemNode;
+use crate::system_path;
 use std::collections::BTreeMap;
 
 pub struct SystemDirectory {
     pub name: String,
     pub contents: BTreeMap<String, SystemNode>,
+    /// Whether everything in this directory is known, because it was listed or created empty.
+    pub listed: bool,
 }
 
 impl SystemDirectory {
@@ -11,6 +16,7 @@ impl SystemDirectory {
         SystemDirectory {
             name,
             contents: BTreeMap::new(),
+            listed: false,
         }
     }
 
@@ -28,13 +34,75 @@ impl SystemDirectory {
             .collect()
     }
 
+    /// Plans the smallest deletions it can find that leave `required_free` space on a disk of
+    /// `disk_size`. See [CleanupPlan::new].
+    pub fn plan_cleanup(
+        &self,
+        disk_size: usize,
+        required_free: usize,
+        protected: &[&str],
+    ) -> Option<CleanupPlan> {
+        CleanupPlan::new(self, disk_size, required_free, protected)
+    }
+
     pub fn size(&self) -> usize {
-        self.contents
-            .values()
-            .map(|node| match node {
-                SystemNode::Directory(dir) => dir.size(),
-                SystemNode::File(_, size) => *size,
-            })
-            .fold(0usize, |acc, val| acc + val)
+        self.contents.values().map(SystemNode::size).sum()
+    }
+
+    /// Returns the directory at `path`, relative to this one.
+    pub fn get_dir(&self, path: &[String]) -> Result<&SystemDirectory, SystemError> {
+        let mut dir = self;
+        for (i, seg) in path.iter().enumerate() {
+            dir = match dir.contents.get(seg) {
+                Some(SystemNode::Directory(inner)) => inner,
+                Some(SystemNode::File(..)) => {
+                    return Err(SystemError::NotADirectory(system_path::format(&path[..=i])))
+                }
+                None => return Err(SystemError::NotFound(system_path::format(&path[..=i]))),
+            };
+        }
+        Ok(dir)
+    }
+
+    /// Returns the directory at `path`, relative to this one.
+    pub fn get_dir_mut(&mut self, path: &[String]) -> Result<&mut SystemDirectory, SystemError> {
+        let mut dir = self;
+        for (i, seg) in path.iter().enumerate() {
+            dir = match dir.contents.get_mut(seg) {
+                Some(SystemNode::Directory(inner)) => inner,
+                Some(SystemNode::File(..)) => {
+                    return Err(SystemError::NotADirectory(system_path::format(&path[..=i])))
+                }
+                None => return Err(SystemError::NotFound(system_path::format(&path[..=i]))),
+            };
+        }
+        Ok(dir)
+    }
+
+    /// Returns the file or directory at `path`, relative to this one, which must not be empty.
+    pub fn get_node(&self, path: &[String]) -> Result<&SystemNode, SystemError> {
+        let (name, parent) = path
+            .split_last()
+            .ok_or_else(|| SystemError::NotFound(system_path::format(path)))?;
+        self.get_dir(parent)?
+            .contents
+            .get(name)
+            .ok_or_else(|| SystemError::NotFound(system_path::format(path)))
+    }
+
+    /// Returns every file and directory inside this one at any depth, each with its path relative
+    /// to this one, parents before their contents.
+    pub fn walk(&self) -> Vec<(Vec<String>, &SystemNode)> {
+        let mut nodes = vec![];
+        for (name, node) in &self.contents {
+            nodes.push((vec![name.clone()], node));
+            if let SystemNode::Directory(dir) = node {
+                for (mut path, inner) in dir.walk() {
+                    path.insert(0, name.clone());
+                    nodes.push((path, inner));
+                }
+            }
+        }
+        nodes
     }
 }
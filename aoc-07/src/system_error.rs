@@ -0,0 +1,38 @@
+use std::fmt::{Display, Formatter};
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum SystemError {
+    UnknownCommand(String),
+    MissingArgument(String),
+    InvalidSize(String),
+    InvalidListing(String),
+    NotFound(String),
+    NotADirectory(String),
+    IsADirectory(String),
+    AlreadyExists(String),
+    InvalidMove(String, String),
+    CannotRemoveRoot,
+}
+
+impl Display for SystemError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SystemError::UnknownCommand(cmd) => write!(f, "{}: command not found", cmd),
+            SystemError::MissingArgument(cmd) => write!(f, "{}: missing argument", cmd),
+            SystemError::InvalidSize(size) => write!(f, "invalid size: {}", size),
+            SystemError::InvalidListing(line) => write!(f, "invalid listing: {}", line),
+            SystemError::NotFound(path) => write!(f, "{}: no such file or directory", path),
+            SystemError::NotADirectory(path) => write!(f, "{}: not a directory", path),
+            SystemError::IsADirectory(path) => write!(f, "{}: is a directory", path),
+            SystemError::AlreadyExists(path) => write!(f, "{}: already exists", path),
+            SystemError::InvalidMove(from, to) => {
+                write!(
+                    f,
+                    "cannot move {} to a subdirectory of itself, {}",
+                    from, to
+                )
+            }
+            SystemError::CannotRemoveRoot => write!(f, "cannot remove /"),
+        }
+    }
+}
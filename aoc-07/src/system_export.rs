@@ -0,0 +1,88 @@
+use crate::system_directory::SystemDirectory;
+use crate::system_node::SystemNode;
+use crate::system_path;
+
+/// Describes every file and directory, including `root` itself, as a JSON object with its full
+/// path, type and recursive size. Returns one object per line, which is NDJSON.
+pub fn to_ndjson(root: &SystemDirectory) -> String {
+    entries(root)
+        .into_iter()
+        .map(|entry| format!("{}\n", entry))
+        .collect()
+}
+
+/// The same objects as [to_ndjson], but as a single JSON array.
+pub fn to_json(root: &SystemDirectory) -> String {
+    format!("[{}]\n", entries(root).join(","))
+}
+
+fn entries(root: &SystemDirectory) -> Vec<String> {
+    let mut entries = vec![entry(&[], "directory", root.size())];
+    for (path, node) in root.walk() {
+        let kind = match node {
+            SystemNode::Directory(_) => "directory",
+            SystemNode::File(..) => "file",
+        };
+        entries.push(entry(&path, kind, node.size()));
+    }
+    entries
+}
+
+fn entry(path: &[String], kind: &str, size: usize) -> String {
+    format!(
+        "{{\"path\":{},\"type\":\"{}\",\"size\":{}}}",
+        json_string(&system_path::format(path)),
+        kind,
+        size
+    )
+}
+
+fn json_string(input: &str) -> String {
+    let mut output = String::from("\"");
+    for c in input.chars() {
+        match c {
+            '"' => output.push_str("\\\""),
+            '\\' => output.push_str("\\\\"),
+            '\n' => output.push_str("\\n"),
+            '\r' => output.push_str("\\r"),
+            '\t' => output.push_str("\\t"),
+            c if (c as u32) < 0x20 => output.push_str(&format!("\\u{:04x}", c as u32)),
+            c => output.push(c),
+        }
+    }
+    output.push('"');
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::system_export::{json_string, to_json, to_ndjson};
+    use crate::system_state::tests::sample;
+
+    #[test]
+    fn exports_ndjson() {
+        let ndjson = to_ndjson(&sample().root);
+        let expected = [
+            "{\"path\":\"/\",\"type\":\"directory\",\"size\":48381165}",
+            "{\"path\":\"/a\",\"type\":\"directory\",\"size\":94853}",
+            "{\"path\":\"/a/e\",\"type\":\"directory\",\"size\":584}",
+            "{\"path\":\"/a/e/i\",\"type\":\"file\",\"size\":584}",
+        ];
+        assert!(ndjson.lines().take(4).eq(expected));
+        assert_eq!(ndjson.lines().count(), 14);
+        assert!(ndjson.ends_with("{\"path\":\"/d/k\",\"type\":\"file\",\"size\":7214296}\n"));
+    }
+
+    #[test]
+    fn exports_json() {
+        let json = to_json(&sample().root);
+        assert!(json.starts_with("[{\"path\":\"/\","));
+        assert!(json.ends_with("\"size\":7214296}]\n"));
+        assert_eq!(json.matches("\"path\"").count(), 14);
+    }
+
+    #[test]
+    fn escapes_strings() {
+        assert_eq!(json_string("a\"b\\c\u{1}"), "\"a\\\"b\\\\c\\u0001\"");
+    }
+}
@@ -13,6 +13,20 @@ impl SystemNode {
             SystemNode::File(name, _) => name.as_str(),
         }
     }
+
+    pub fn set_name(&mut self, name: String) {
+        match self {
+            SystemNode::Directory(dir) => dir.name = name,
+            SystemNode::File(file_name, _) => *file_name = name,
+        }
+    }
+
+    pub fn size(&self) -> usize {
+        match self {
+            SystemNode::Directory(dir) => dir.size(),
+            SystemNode::File(_, size) => *size,
+        }
+    }
 }
 
 impl From<ListNode> for SystemNode {
@@ -0,0 +1,47 @@
+/// Resolves `path` against the working directory `cwd`, returning the segments of the absolute
+/// path it names. Handles `.` and `..`, where `..` at the root stays at the root.
+pub fn resolve(cwd: &[String], path: &str) -> Vec<String> {
+    let mut segments = if path.starts_with('/') {
+        vec![]
+    } else {
+        cwd.to_vec()
+    };
+    for seg in path.split('/') {
+        match seg {
+            "" | "." => {}
+            ".." => {
+                segments.pop();
+            }
+            s => segments.push(s.to_string()),
+        }
+    }
+    segments
+}
+
+/// Formats the segments of an absolute path, like `/a/b`.
+pub fn format(segments: &[String]) -> String {
+    format!("/{}", segments.join("/"))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::system_path::{format, resolve};
+
+    fn cwd() -> Vec<String> {
+        vec!["a".to_string(), "b".to_string()]
+    }
+
+    #[test]
+    fn resolves_relative_paths() {
+        assert_eq!(format(&resolve(&cwd(), "c")), "/a/b/c");
+        assert_eq!(format(&resolve(&cwd(), "./c/../d/")), "/a/b/d");
+        assert_eq!(format(&resolve(&cwd(), "..")), "/a");
+        assert_eq!(format(&resolve(&cwd(), "../../../..")), "/");
+    }
+
+    #[test]
+    fn resolves_absolute_paths() {
+        assert_eq!(format(&resolve(&cwd(), "/")), "/");
+        assert_eq!(format(&resolve(&cwd(), "/x//y")), "/x/y");
+    }
+}
@@ -1,6 +1,8 @@
-use crate::system_command::{ListNode, SystemCommand};
+use crate::system_command::{ListNode, SizeFilter, SystemCommand};
 use crate::system_directory::SystemDirectory;
+use crate::system_error::SystemError;
 use crate::system_node::SystemNode;
+use crate::system_path;
 
 pub struct SystemState {
     pub root: SystemDirectory,
@@ -8,42 +10,197 @@ pub struct SystemState {
 }
 
 impl SystemState {
-    pub fn run(&mut self, command: SystemCommand) {
+    /// Runs `command` against the filesystem, returning the lines it would print.
+    pub fn run(&mut self, command: SystemCommand) -> Result<Vec<String>, SystemError> {
         match command {
-            SystemCommand::ChangeDirectory(dir) => self.cd(dir),
+            SystemCommand::ChangeDirectory(dir) => self.cd(&dir),
             SystemCommand::List(res) => self.ls(res),
+            SystemCommand::MakeDirectory(path) => self.mkdir(&path),
+            SystemCommand::Touch(size, path) => self.touch(size, &path),
+            SystemCommand::Remove(path) => self.rm(&path),
+            SystemCommand::Move(from, to) => self.mv(&from, &to),
+            SystemCommand::PrintWorkingDirectory => Ok(vec![system_path::format(&self.cwd)]),
+            SystemCommand::DiskUsage(path) => self.du(&path),
+            SystemCommand::Find(path, filter) => self.find(&path, filter),
         }
     }
 
-    fn cd(&mut self, dir: String) {
-        if dir == ".." {
-            self.cwd.pop();
-        } else if dir == "/" {
-            self.cwd.clear();
-        } else {
-            self.cwd.push(dir)
-        }
+    pub fn cwd(&self) -> &[String] {
+        &self.cwd
+    }
+
+    fn cd(&mut self, dir: &str) -> Result<Vec<String>, SystemError> {
+        let path = system_path::resolve(&self.cwd, dir);
+        self.root.get_dir(&path)?;
+        self.cwd = path;
+        Ok(vec![])
     }
 
-    fn ls(&mut self, result: Vec<ListNode>) {
-        let dir = self.enter_cwd();
+    /// Adds the listed nodes to the working directory, then prints everything in it. Directories
+    /// listed again keep their contents, anything else listed again is replaced.
+    fn ls(&mut self, result: Vec<ListNode>) -> Result<Vec<String>, SystemError> {
+        let dir = self.root.get_dir_mut(&self.cwd)?;
         for node in result {
             let sys_node = SystemNode::from(node);
-            dir.contents.insert(sys_node.name().to_string(), sys_node);
+            match (dir.contents.get(sys_node.name()), &sys_node) {
+                (Some(SystemNode::Directory(_)), SystemNode::Directory(_)) => {}
+                _ => {
+                    dir.contents.insert(sys_node.name().to_string(), sys_node);
+                }
+            }
         }
+        dir.listed = true;
+        let output = dir
+            .contents
+            .values()
+            .map(|node| match node {
+                SystemNode::Directory(inner) => format!("dir {}", inner.name),
+                SystemNode::File(name, size) => format!("{} {}", size, name),
+            })
+            .collect();
+        Ok(output)
     }
 
-    fn enter_cwd(&mut self) -> &mut SystemDirectory {
-        let mut dir = &mut self.root;
-        for seg in self.cwd.iter() {
-            let inner = dir.contents.get_mut(seg).unwrap();
-            if let SystemNode::Directory(i) = inner {
-                dir = i;
-            } else {
-                panic!("Path segment {} is not a directory!", seg);
+    fn mkdir(&mut self, path: &str) -> Result<Vec<String>, SystemError> {
+        let path = system_path::resolve(&self.cwd, path);
+        let (parent, name) = self.enter_parent(&path)?;
+        if parent.contents.contains_key(&name) {
+            return Err(SystemError::AlreadyExists(system_path::format(&path)));
+        }
+        let mut dir = SystemDirectory::new(name.clone());
+        dir.listed = true;
+        parent.contents.insert(name, SystemNode::Directory(dir));
+        Ok(vec![])
+    }
+
+    /// Creates a file, or changes the size of an existing one.
+    fn touch(&mut self, size: usize, path: &str) -> Result<Vec<String>, SystemError> {
+        let path = system_path::resolve(&self.cwd, path);
+        let (parent, name) = self.enter_parent(&path)?;
+        if let Some(SystemNode::Directory(_)) = parent.contents.get(&name) {
+            return Err(SystemError::IsADirectory(system_path::format(&path)));
+        }
+        parent
+            .contents
+            .insert(name.clone(), SystemNode::File(name, size));
+        Ok(vec![])
+    }
+
+    /// Removes a file, or a directory and everything in it.
+    fn rm(&mut self, path: &str) -> Result<Vec<String>, SystemError> {
+        let path = system_path::resolve(&self.cwd, path);
+        if path.is_empty() {
+            return Err(SystemError::CannotRemoveRoot);
+        }
+        let (parent, name) = self.enter_parent(&path)?;
+        parent
+            .contents
+            .remove(&name)
+            .ok_or_else(|| SystemError::NotFound(system_path::format(&path)))?;
+        Ok(vec![])
+    }
+
+    /// Moves or renames a file or directory. Moving onto an existing directory moves into it,
+    /// and moving a file onto an existing file replaces it.
+    fn mv(&mut self, from: &str, to: &str) -> Result<Vec<String>, SystemError> {
+        let from = system_path::resolve(&self.cwd, from);
+        let mut to = system_path::resolve(&self.cwd, to);
+        if from.is_empty() {
+            return Err(SystemError::CannotRemoveRoot);
+        }
+        let is_dir = matches!(self.root.get_node(&from)?, SystemNode::Directory(_));
+        if self.root.get_dir(&to).is_ok() {
+            to.push(from.last().unwrap().clone());
+        }
+        if to == from {
+            return Ok(vec![]);
+        }
+        if to.starts_with(&from) {
+            return Err(SystemError::InvalidMove(
+                system_path::format(&from),
+                system_path::format(&to),
+            ));
+        }
+        match self.root.get_node(&to) {
+            Ok(SystemNode::File(..)) if !is_dir => {}
+            Ok(SystemNode::File(..)) => {
+                return Err(SystemError::NotADirectory(system_path::format(&to)))
+            }
+            Ok(SystemNode::Directory(_)) => {
+                return Err(SystemError::AlreadyExists(system_path::format(&to)))
             }
+            Err(SystemError::NotFound(_)) => {}
+            Err(e) => return Err(e),
         }
-        dir
+        self.enter_parent(&to)?;
+
+        let (from_parent, from_name) = self.enter_parent(&from)?;
+        let mut node = from_parent.contents.remove(&from_name).unwrap();
+        let (to_parent, to_name) = self.enter_parent(&to)?;
+        node.set_name(to_name.clone());
+        to_parent.contents.insert(to_name, node);
+        Ok(vec![])
+    }
+
+    /// Prints the size and path of every directory under `path`, contents first, or just the file
+    /// at `path`.
+    fn du(&self, path: &str) -> Result<Vec<String>, SystemError> {
+        let path = system_path::resolve(&self.cwd, path);
+        let dir = if path.is_empty() {
+            &self.root
+        } else {
+            match self.root.get_node(&path)? {
+                SystemNode::Directory(dir) => dir,
+                SystemNode::File(_, size) => {
+                    return Ok(vec![format!("{}\t{}", size, system_path::format(&path))])
+                }
+            }
+        };
+        let mut output: Vec<String> = dir
+            .walk()
+            .into_iter()
+            .rev()
+            .filter(|(_, node)| matches!(node, SystemNode::Directory(_)))
+            .map(|(inner, node)| {
+                let full: Vec<String> = path.iter().chain(inner.iter()).cloned().collect();
+                format!("{}\t{}", node.size(), system_path::format(&full))
+            })
+            .collect();
+        output.push(format!("{}\t{}", dir.size(), system_path::format(&path)));
+        Ok(output)
+    }
+
+    /// Prints the path of every file under `path` whose size matches `filter`.
+    fn find(&self, path: &str, filter: SizeFilter) -> Result<Vec<String>, SystemError> {
+        let path = system_path::resolve(&self.cwd, path);
+        let nodes = if path.is_empty() {
+            self.root.walk()
+        } else {
+            match self.root.get_node(&path)? {
+                SystemNode::Directory(dir) => dir.walk(),
+                file => vec![(vec![], file)],
+            }
+        };
+        let output = nodes
+            .into_iter()
+            .filter(|(_, node)| matches!(node, SystemNode::File(_, size) if filter.matches(*size)))
+            .map(|(inner, _)| {
+                let full: Vec<String> = path.iter().chain(inner.iter()).cloned().collect();
+                system_path::format(&full)
+            })
+            .collect();
+        Ok(output)
+    }
+
+    /// Returns the directory that contains `path`, along with the last segment of `path`.
+    fn enter_parent(
+        &mut self,
+        path: &[String],
+    ) -> Result<(&mut SystemDirectory, String), SystemError> {
+        let (name, parent) = path
+            .split_last()
+            .ok_or_else(|| SystemError::AlreadyExists(system_path::format(path)))?;
+        Ok((self.root.get_dir_mut(parent)?, name.clone()))
     }
 }
 
@@ -56,3 +213,161 @@ impl Default for SystemState {
         }
     }
 }
+
+#[cfg(test)]
+pub mod tests {
+    use crate::system_command::SystemCommand;
+    use crate::system_error::SystemError;
+    use crate::system_state::SystemState;
+
+    fn run(state: &mut SystemState, line: &str) -> Result<Vec<String>, SystemError> {
+        state.run(SystemCommand::try_from(line)?)
+    }
+
+    /// The filesystem from the puzzle's example.
+    pub fn sample() -> SystemState {
+        let mut state = SystemState::default();
+        for line in [
+            "$ mkdir a",
+            "$ mkdir a/e",
+            "$ touch 584 a/e/i",
+            "$ touch 29116 a/f",
+            "$ touch 2557 a/g",
+            "$ touch 62596 a/h.lst",
+            "$ touch 14848514 b.txt",
+            "$ touch 8504156 c.dat",
+            "$ mkdir d",
+            "$ cd d",
+            "$ touch 4060174 j",
+            "$ touch 8033020 d.log",
+            "$ touch 5626152 d.ext",
+            "$ touch 7214296 k",
+            "$ cd ..",
+        ] {
+            run(&mut state, line).unwrap();
+        }
+        state
+    }
+
+    #[test]
+    fn builds_filesystem() {
+        let state = sample();
+        assert_eq!(state.root.size(), 48381165);
+        assert_eq!(state.root.flat_directories().len(), 4);
+    }
+
+    #[test]
+    fn pwd_and_cd() {
+        let mut state = sample();
+        assert_eq!(run(&mut state, "$ pwd").unwrap(), ["/"]);
+        run(&mut state, "$ cd a/e").unwrap();
+        assert_eq!(run(&mut state, "$ pwd").unwrap(), ["/a/e"]);
+        run(&mut state, "$ cd ../../d").unwrap();
+        assert_eq!(run(&mut state, "$ pwd").unwrap(), ["/d"]);
+        assert_eq!(
+            run(&mut state, "$ cd /nope"),
+            Err(SystemError::NotFound("/nope".to_string()))
+        );
+        assert_eq!(
+            run(&mut state, "$ cd /b.txt"),
+            Err(SystemError::NotADirectory("/b.txt".to_string()))
+        );
+        assert_eq!(run(&mut state, "$ pwd").unwrap(), ["/d"]);
+    }
+
+    #[test]
+    fn ls_prints_contents() {
+        let mut state = sample();
+        run(&mut state, "$ cd /a").unwrap();
+        assert_eq!(
+            run(&mut state, "$ ls").unwrap(),
+            ["dir e", "29116 f", "2557 g", "62596 h.lst"]
+        );
+    }
+
+    #[test]
+    fn du_and_find() {
+        let mut state = sample();
+        assert_eq!(
+            run(&mut state, "$ du").unwrap(),
+            ["24933642\t/d", "584\t/a/e", "94853\t/a", "48381165\t/"]
+        );
+        assert_eq!(run(&mut state, "$ du /a/f").unwrap(), ["29116\t/a/f"]);
+        assert_eq!(
+            run(&mut state, "$ find -size +8033020").unwrap(),
+            ["/b.txt", "/c.dat"]
+        );
+        run(&mut state, "$ cd a").unwrap();
+        assert_eq!(
+            run(&mut state, "$ find . -size -3000").unwrap(),
+            ["/a/e/i", "/a/g"]
+        );
+        assert_eq!(run(&mut state, "$ find g -size 2557").unwrap(), ["/a/g"]);
+    }
+
+    #[test]
+    fn rm_and_mv() {
+        let mut state = sample();
+        run(&mut state, "$ rm /a/e").unwrap();
+        assert_eq!(state.root.size(), 48381165 - 584);
+        assert_eq!(
+            run(&mut state, "$ rm /a/e"),
+            Err(SystemError::NotFound("/a/e".to_string()))
+        );
+        assert_eq!(
+            run(&mut state, "$ rm /"),
+            Err(SystemError::CannotRemoveRoot)
+        );
+
+        run(&mut state, "$ mv /a /d").unwrap();
+        run(&mut state, "$ mv /d/j /d/a/k").unwrap();
+        run(&mut state, "$ mv b.txt c.dat").unwrap();
+        assert_eq!(
+            run(&mut state, "$ find / -size +0").unwrap(),
+            [
+                "/c.dat",
+                "/d/a/f",
+                "/d/a/g",
+                "/d/a/h.lst",
+                "/d/a/k",
+                "/d/d.ext",
+                "/d/d.log",
+                "/d/k"
+            ]
+        );
+        assert_eq!(
+            run(&mut state, "$ du /c.dat").unwrap(),
+            ["14848514\t/c.dat"]
+        );
+        assert_eq!(
+            run(&mut state, "$ mv /d /d/a"),
+            Err(SystemError::InvalidMove(
+                "/d".to_string(),
+                "/d/a/d".to_string()
+            ))
+        );
+        assert_eq!(
+            run(&mut state, "$ mv /d /c.dat"),
+            Err(SystemError::NotADirectory("/c.dat".to_string()))
+        );
+    }
+
+    #[test]
+    fn mkdir_and_touch_errors() {
+        let mut state = sample();
+        assert_eq!(
+            run(&mut state, "$ mkdir a"),
+            Err(SystemError::AlreadyExists("/a".to_string()))
+        );
+        assert_eq!(
+            run(&mut state, "$ mkdir x/y"),
+            Err(SystemError::NotFound("/x".to_string()))
+        );
+        assert_eq!(
+            run(&mut state, "$ touch 5 a"),
+            Err(SystemError::IsADirectory("/a".to_string()))
+        );
+        run(&mut state, "$ touch 5 b.txt").unwrap();
+        assert_eq!(state.root.size(), 48381165 - 14848514 + 5);
+    }
+}
@@ -0,0 +1,296 @@
+use crate::system_command::{ListNode, SystemCommand};
+use crate::system_error::SystemError;
+use crate::system_node::SystemNode;
+use crate::system_path;
+use crate::system_state::SystemState;
+use std::collections::BTreeSet;
+use std::fmt::{Display, Formatter};
+
+/// A problem found while replaying a transcript. Commands are numbered from 1.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum TranscriptIssue {
+    Failed {
+        command: usize,
+        error: SystemError,
+    },
+    SizeChanged {
+        command: usize,
+        path: String,
+        before: usize,
+        after: usize,
+    },
+    KindChanged {
+        command: usize,
+        path: String,
+    },
+    Dropped {
+        command: usize,
+        path: String,
+    },
+    NeverListed {
+        path: String,
+    },
+}
+
+impl Display for TranscriptIssue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TranscriptIssue::Failed { command, error } => {
+                write!(f, "command {}: {}", command, error)
+            }
+            TranscriptIssue::SizeChanged {
+                command,
+                path,
+                before,
+                after,
+            } => write!(
+                f,
+                "command {}: {} listed with size {}, previously {}",
+                command, path, after, before
+            ),
+            TranscriptIssue::KindChanged { command, path } => write!(
+                f,
+                "command {}: {} listed as both a file and a directory",
+                command, path
+            ),
+            TranscriptIssue::Dropped { command, path } => write!(
+                f,
+                "command {}: {} was listed before but is missing",
+                command, path
+            ),
+            TranscriptIssue::NeverListed { path } => {
+                write!(f, "{}: entered but never listed", path)
+            }
+        }
+    }
+}
+
+/// Replays a transcript of commands, checking that its listings agree with each other.
+pub struct TranscriptReport {
+    pub state: SystemState,
+    issues: Vec<TranscriptIssue>,
+}
+
+impl TranscriptReport {
+    pub fn new(commands: Vec<SystemCommand>) -> Self {
+        let mut state = SystemState::default();
+        let mut issues = Vec::new();
+        let mut entered = BTreeSet::new();
+        for (i, command) in commands.into_iter().enumerate() {
+            if let SystemCommand::List(nodes) = &command {
+                issues.extend(TranscriptReport::check_listing(&state, i + 1, nodes));
+            }
+            let is_cd = matches!(command, SystemCommand::ChangeDirectory(_));
+            match state.run(command) {
+                Ok(_) if is_cd => {
+                    entered.insert(state.cwd().to_vec());
+                }
+                Ok(_) => {}
+                Err(error) => issues.push(TranscriptIssue::Failed {
+                    command: i + 1,
+                    error,
+                }),
+            }
+        }
+
+        for path in entered {
+            if state.root.get_dir(&path).is_ok_and(|dir| !dir.listed) {
+                issues.push(TranscriptIssue::NeverListed {
+                    path: system_path::format(&path),
+                });
+            }
+        }
+        TranscriptReport { state, issues }
+    }
+
+    pub fn issues(&self) -> &[TranscriptIssue] {
+        &self.issues
+    }
+
+    /// Whether no listing contradicts an earlier one. Failed commands and unlisted directories
+    /// only make a transcript incomplete.
+    pub fn is_consistent(&self) -> bool {
+        !self.issues.iter().any(|issue| {
+            matches!(
+                issue,
+                TranscriptIssue::SizeChanged { .. }
+                    | TranscriptIssue::KindChanged { .. }
+                    | TranscriptIssue::Dropped { .. }
+            )
+        })
+    }
+
+    /// Whether every directory was listed, so that the computed sizes are exact. Otherwise, they
+    /// are only lower bounds.
+    pub fn is_complete(&self) -> bool {
+        self.state.root.listed
+            && self.state.root.walk().iter().all(|(_, node)| match node {
+                SystemNode::Directory(dir) => dir.listed,
+                SystemNode::File(..) => true,
+            })
+    }
+
+    /// Compares a listing of the working directory against what earlier listings said about it,
+    /// including entries an earlier listing had that this one leaves out.
+    fn check_listing(
+        state: &SystemState,
+        command: usize,
+        nodes: &[ListNode],
+    ) -> Vec<TranscriptIssue> {
+        let Ok(dir) = state.root.get_dir(state.cwd()) else {
+            return Vec::new();
+        };
+        let path_of = |name: &str| {
+            let mut path = state.cwd().to_vec();
+            path.push(name.to_string());
+            system_path::format(&path)
+        };
+        let mut issues: Vec<TranscriptIssue> = nodes
+            .iter()
+            .filter_map(|node| match (node, dir.contents.get(node.name())?) {
+                (ListNode::File(name, after), SystemNode::File(_, before)) if after != before => {
+                    Some(TranscriptIssue::SizeChanged {
+                        command,
+                        path: path_of(name),
+                        before: *before,
+                        after: *after,
+                    })
+                }
+                (ListNode::File(name, _), SystemNode::Directory(_))
+                | (ListNode::Directory(name), SystemNode::File(..)) => {
+                    Some(TranscriptIssue::KindChanged {
+                        command,
+                        path: path_of(name),
+                    })
+                }
+                _ => None,
+            })
+            .collect();
+        if dir.listed {
+            issues.extend(
+                dir.contents
+                    .keys()
+                    .filter(|name| nodes.iter().all(|node| node.name() != name.as_str()))
+                    .map(|name| TranscriptIssue::Dropped {
+                        command,
+                        path: path_of(name),
+                    }),
+            );
+        }
+        issues
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::system_command::{ListNode, SystemCommand};
+    use crate::system_error::SystemError;
+    use crate::system_transcript::{TranscriptIssue, TranscriptReport};
+
+    fn replay(transcript: &[&str]) -> TranscriptReport {
+        let mut commands: Vec<SystemCommand> = Vec::new();
+        for line in transcript {
+            if line.starts_with('$') {
+                commands.push(SystemCommand::try_from(*line).unwrap());
+            } else if let Some(SystemCommand::List(nodes)) = commands.last_mut() {
+                nodes.push(ListNode::try_from(*line).unwrap());
+            }
+        }
+        TranscriptReport::new(commands)
+    }
+
+    #[test]
+    fn consistent_and_complete() {
+        let report = replay(&[
+            "$ cd /", "$ ls", "dir a", "14 b", "$ cd a", "$ ls", "10 c", "$ cd /", "$ ls", "dir a",
+            "14 b",
+        ]);
+        assert!(report.is_consistent());
+        assert!(report.is_complete());
+        assert_eq!(report.state.root.size(), 24);
+    }
+
+    #[test]
+    fn relisting_keeps_contents() {
+        let report = replay(&[
+            "$ ls", "dir a", "$ cd a", "$ ls", "10 c", "$ cd ..", "$ ls", "dir a",
+        ]);
+        assert!(report.is_complete());
+        assert_eq!(report.state.root.size(), 10);
+    }
+
+    #[test]
+    fn reports_conflicting_listings() {
+        let report = replay(&[
+            "$ ls", "dir a", "14 b", "5 c", "$ ls", "3 a", "dir b", "20 c",
+        ]);
+        assert_eq!(
+            report.issues(),
+            [
+                TranscriptIssue::KindChanged {
+                    command: 2,
+                    path: "/a".to_string()
+                },
+                TranscriptIssue::KindChanged {
+                    command: 2,
+                    path: "/b".to_string()
+                },
+                TranscriptIssue::SizeChanged {
+                    command: 2,
+                    path: "/c".to_string(),
+                    before: 5,
+                    after: 20
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_dropped_entries() {
+        let report = replay(&[
+            "$ ls", "dir a", "14 b", "5 c", "$ ls", "dir a", "$ cd a", "$ ls", "$ ls", "1 d",
+        ]);
+        assert_eq!(
+            report.issues(),
+            [
+                TranscriptIssue::Dropped {
+                    command: 2,
+                    path: "/b".to_string()
+                },
+                TranscriptIssue::Dropped {
+                    command: 2,
+                    path: "/c".to_string()
+                },
+            ]
+        );
+        assert!(!report.is_consistent());
+    }
+
+    #[test]
+    fn reports_unlisted_directories() {
+        let report = replay(&[
+            "$ ls", "dir a", "dir b", "$ cd a", "$ cd /b", "$ ls", "$ cd /c",
+        ]);
+        assert_eq!(
+            report.issues(),
+            [
+                TranscriptIssue::Failed {
+                    command: 5,
+                    error: SystemError::NotFound("/c".to_string())
+                },
+                TranscriptIssue::NeverListed {
+                    path: "/a".to_string()
+                },
+            ]
+        );
+        assert!(report.is_consistent());
+        assert!(!report.is_complete());
+    }
+
+    #[test]
+    fn unlisted_root_is_incomplete() {
+        let report = replay(&["$ mkdir a", "$ touch 5 a/b"]);
+        assert!(report.is_consistent());
+        assert!(!report.is_complete());
+    }
+}
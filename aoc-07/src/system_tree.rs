@@ -0,0 +1,117 @@
+use crate::system_directory::SystemDirectory;
+use crate::system_node::SystemNode;
+use std::fmt::{Display, Formatter};
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum TreeSort {
+    /// Alphabetically by name.
+    Name,
+    /// Largest first, then alphabetically by name.
+    Size,
+}
+
+/// Draws a directory and everything in it like the `tree` command, with the recursive size of
+/// each entry. Entries deeper than `max_depth` are left out, though still counted in sizes.
+pub struct SystemTree<'a> {
+    pub root: &'a SystemDirectory,
+    pub sort: TreeSort,
+    pub max_depth: Option<usize>,
+}
+
+impl<'a> SystemTree<'a> {
+    pub fn new(root: &'a SystemDirectory) -> Self {
+        SystemTree {
+            root,
+            sort: TreeSort::Name,
+            max_depth: None,
+        }
+    }
+
+    fn write_contents(
+        &self,
+        f: &mut Formatter<'_>,
+        dir: &SystemDirectory,
+        prefix: &str,
+        depth: usize,
+    ) -> std::fmt::Result {
+        if self.max_depth.is_some_and(|max| depth > max) {
+            return Ok(());
+        }
+        let mut nodes: Vec<&SystemNode> = dir.contents.values().collect();
+        if self.sort == TreeSort::Size {
+            nodes.sort_by_key(|n| std::cmp::Reverse(n.size()));
+        }
+        for (i, node) in nodes.iter().enumerate() {
+            let last = i == nodes.len() - 1;
+            let (branch, indent) = if last {
+                ("└── ", "    ")
+            } else {
+                ("├── ", "│   ")
+            };
+            match node {
+                SystemNode::Directory(inner) => {
+                    writeln!(f, "{}{}{}/ ({})", prefix, branch, inner.name, inner.size())?;
+                    let prefix = format!("{}{}", prefix, indent);
+                    self.write_contents(f, inner, &prefix, depth + 1)?;
+                }
+                SystemNode::File(name, size) => {
+                    writeln!(f, "{}{}{} ({})", prefix, branch, name, size)?
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Display for SystemTree<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "{}/ ({})", self.root.name, self.root.size())?;
+        self.write_contents(f, self.root, "", 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::system_state::tests::sample;
+    use crate::system_tree::{SystemTree, TreeSort};
+
+    #[test]
+    fn renders_by_name() {
+        let state = sample();
+        let expected = concat!(
+            "/ (48381165)\n",
+            "├── a/ (94853)\n",
+            "│   ├── e/ (584)\n",
+            "│   │   └── i (584)\n",
+            "│   ├── f (29116)\n",
+            "│   ├── g (2557)\n",
+            "│   └── h.lst (62596)\n",
+            "├── b.txt (14848514)\n",
+            "├── c.dat (8504156)\n",
+            "└── d/ (24933642)\n",
+            "    ├── d.ext (5626152)\n",
+            "    ├── d.log (8033020)\n",
+            "    ├── j (4060174)\n",
+            "    └── k (7214296)\n",
+        );
+        assert_eq!(SystemTree::new(&state.root).to_string(), expected);
+    }
+
+    #[test]
+    fn renders_by_size_with_depth_limit() {
+        let state = sample();
+        let tree = SystemTree {
+            root: &state.root,
+            sort: TreeSort::Size,
+            max_depth: Some(1),
+        };
+        let expected = concat!(
+            "/ (48381165)\n",
+            "├── d/ (24933642)\n",
+            "├── b.txt (14848514)\n",
+            "├── c.dat (8504156)\n",
+            "└── a/ (94853)\n",
+        );
+        assert_eq!(tree.to_string(), expected);
+    }
+}
@@ -1,100 +1,255 @@
+use crate::forest_error::ForestError;
 use crate::tree::Tree;
-use std::ops::Index;
 use std::str::Lines;
 
 type TreeLocation = (usize, usize);
 
+/// Marks a spot in the input where no tree grows.
+pub const HOLE: &str = ".";
+
+/// A grid of trees, which may have holes and rows of different lengths. Sight lines pass over
+/// holes and past the ends of short rows, and a tree with no other trees beyond it in some
+/// direction is on the edge of the forest in that direction.
 pub struct Forest {
-    trees: Vec<Vec<Tree>>,
+    trees: Vec<Vec<Option<Tree>>>,
+    visible: Vec<Vec<bool>>,
+    scenic_scores: Vec<Vec<usize>>,
 }
 
 impl Forest {
+    pub fn new(trees: Vec<Vec<Option<Tree>>>) -> Self {
+        let mut forest = Forest {
+            visible: trees.iter().map(|row| vec![false; row.len()]).collect(),
+            scenic_scores: trees
+                .iter()
+                .map(|row| row.iter().map(|t| t.is_some() as usize).collect())
+                .collect(),
+            trees,
+        };
+        for line in forest.lines() {
+            let heights: Vec<Option<Tree>> = line.iter().map(|loc| forest.get(loc)).collect();
+            let visible = visible_from_start(&heights);
+            let distances = viewing_distances(&heights);
+            for (i, (x, y)) in line.into_iter().enumerate() {
+                if heights[i].is_some() {
+                    forest.visible[y][x] |= visible[i];
+                    forest.scenic_scores[y][x] *= distances[i];
+                }
+            }
+        }
+        forest
+    }
+
+    /// The tree at `loc`, or `None` for a hole or a spot outside the forest.
+    pub fn get(&self, loc: &TreeLocation) -> Option<Tree> {
+        *self.trees.get(loc.1)?.get(loc.0)?
+    }
+
+    pub fn rows(&self) -> &[Vec<Option<Tree>>] {
+        &self.trees
+    }
+
+    pub fn width(&self) -> usize {
+        self.trees.iter().map(Vec::len).max().unwrap_or_default()
+    }
+
     pub fn iter_trees(&self) -> impl Iterator<Item = TreeLocation> + '_ {
-        self.trees
-            .iter()
-            .enumerate()
-            .flat_map(|(y, row)| row.iter().enumerate().map(move |(x, _)| (x, y)))
+        self.trees.iter().enumerate().flat_map(|(y, row)| {
+            row.iter()
+                .enumerate()
+                .filter(|(_, tree)| tree.is_some())
+                .map(move |(x, _)| (x, y))
+        })
     }
 
     pub fn is_visible(&self, loc: &TreeLocation) -> bool {
-        self.is_edge(loc)
-            || self.is_visible_direction(loc, self.iter_left(loc))
-            || self.is_visible_direction(loc, self.iter_right(loc))
-            || self.is_visible_direction(loc, self.iter_up(loc))
-            || self.is_visible_direction(loc, self.iter_down(loc))
+        self.visibility()[loc.1][loc.0]
     }
 
     pub fn get_scenic_score(&self, loc: &TreeLocation) -> usize {
-        self.get_scenic_score_direction(loc, self.iter_left(loc))
-            * self.get_scenic_score_direction(loc, self.iter_right(loc))
-            * self.get_scenic_score_direction(loc, self.iter_up(loc))
-            * self.get_scenic_score_direction(loc, self.iter_down(loc))
-    }
-
-    fn is_edge(&self, loc: &TreeLocation) -> bool {
-        loc.0 == 0
-            || loc.1 == 0
-            || loc.1 == self.trees.len() - 1
-            || loc.0 == self.trees[loc.1].len() - 1
-    }
-
-    fn is_visible_direction<T>(&self, loc: &TreeLocation, mut dir: T) -> bool
-    where
-        T: Iterator<Item = TreeLocation>,
-    {
-        let tree = &self[loc];
-        dir.all(|other| &self[&other] < tree)
-    }
-
-    fn get_scenic_score_direction<T>(&self, loc: &TreeLocation, dir: T) -> usize
-    where
-        T: Iterator<Item = TreeLocation>,
-    {
-        let tree = &self[loc];
-        let mut count = 0;
-        for other in dir {
-            count += 1;
-            if &self[&other] >= tree {
-                break;
-            }
-        }
-        count
+        self.scenic_scores()[loc.1][loc.0]
     }
 
-    fn iter_left(&self, loc: &TreeLocation) -> impl Iterator<Item = TreeLocation> + '_ {
-        let y = loc.1;
-        (0..loc.0).rev().map(move |x| (x, y))
+    /// Whether each tree can be seen from outside the forest, indexed like the trees.
+    pub fn visibility(&self) -> &[Vec<bool>] {
+        &self.visible
     }
 
-    fn iter_right(&self, loc: &TreeLocation) -> impl Iterator<Item = TreeLocation> + '_ {
-        let y = loc.1;
-        ((loc.0 + 1)..self.trees[y].len()).map(move |x| (x, y))
+    /// The scenic score of each tree, indexed like the trees.
+    pub fn scenic_scores(&self) -> &[Vec<usize>] {
+        &self.scenic_scores
     }
 
-    fn iter_up(&self, loc: &TreeLocation) -> impl Iterator<Item = TreeLocation> + '_ {
-        let x = loc.0;
-        (0..loc.1).rev().map(move |y| (x, y))
+    /// The tree with the highest scenic score, the first one in reading order if there's a tie.
+    pub fn best_tree(&self) -> Option<TreeLocation> {
+        self.iter_trees().reduce(|best, loc| {
+            match self.get_scenic_score(&loc) > self.get_scenic_score(&best) {
+                true => loc,
+                false => best,
+            }
+        })
     }
 
-    fn iter_down(&self, loc: &TreeLocation) -> impl Iterator<Item = TreeLocation> + '_ {
-        let x = loc.0;
-        ((loc.1 + 1)..self.trees.len()).map(move |y| (x, y))
+    /// Every row and column of the forest, in both directions, each starting at the edge that
+    /// its trees look towards. Columns run the full height, through the ends of short rows.
+    fn lines(&self) -> Vec<Vec<TreeLocation>> {
+        let rows = self
+            .trees
+            .iter()
+            .enumerate()
+            .map(|(y, row)| (0..row.len()).map(|x| (x, y)).collect());
+        let columns = (0..self.width()).map(|x| (0..self.trees.len()).map(|y| (x, y)).collect());
+        rows.chain(columns)
+            .flat_map(|line: Vec<TreeLocation>| {
+                let reversed = line.iter().rev().copied().collect();
+                [line, reversed]
+            })
+            .collect()
     }
 }
 
-impl Index<&TreeLocation> for Forest {
-    type Output = Tree;
+/// Whether each tree is taller than every tree before it. Holes are never visible.
+fn visible_from_start(heights: &[Option<Tree>]) -> Vec<bool> {
+    let mut tallest: Option<Tree> = None;
+    heights
+        .iter()
+        .map(|tree| {
+            let visible = tree.is_some() && *tree > tallest;
+            tallest = tallest.max(*tree);
+            visible
+        })
+        .collect()
+}
 
-    fn index(&self, index: &TreeLocation) -> &Self::Output {
-        &self.trees[index.1][index.0]
+/// How far each tree can see looking back towards the start, up to and including the first tree
+/// at least as tall as itself, or else up to the first tree in the line. Keeps a stack of the
+/// trees that could still block the view of a later tree, so that each tree is pushed and popped
+/// at most once.
+fn viewing_distances(heights: &[Option<Tree>]) -> Vec<usize> {
+    let mut first: Option<usize> = None;
+    let mut blocking: Vec<usize> = Vec::new();
+    let mut distances = vec![0; heights.len()];
+    for (i, tree) in heights.iter().enumerate() {
+        if tree.is_none() {
+            continue;
+        }
+        while blocking.last().is_some_and(|b| heights[*b] < *tree) {
+            blocking.pop();
+        }
+        let start = *first.get_or_insert(i);
+        distances[i] = i - blocking.last().copied().unwrap_or(start);
+        blocking.push(i);
+    }
+    distances
+}
+
+impl TryFrom<Lines<'_>> for Forest {
+    type Error = ForestError;
+
+    /// Reads one row per line, either as a digit per tree or, if any line has a comma, as
+    /// comma-separated heights. [HOLE] marks a spot without a tree in either format.
+    fn try_from(input: Lines<'_>) -> Result<Self, Self::Error> {
+        let lines: Vec<&str> = input.collect();
+        let separated = lines.iter().any(|line| line.contains(','));
+        let mut trees = Vec::new();
+        for (y, line) in lines.iter().enumerate() {
+            let cells: Vec<&str> = match separated {
+                true => line.split(',').map(str::trim).collect(),
+                false => line
+                    .char_indices()
+                    .map(|(i, c)| &line[i..i + c.len_utf8()])
+                    .collect(),
+            };
+            let row = cells
+                .into_iter()
+                .enumerate()
+                .map(|(x, cell)| match cell {
+                    HOLE => Ok(None),
+                    _ => Tree::try_from(cell).map(Some).map_err(|_| ForestError {
+                        line: y + 1,
+                        column: x + 1,
+                        value: cell.to_string(),
+                    }),
+                })
+                .collect::<Result<_, _>>()?;
+            trees.push(row);
+        }
+        Ok(Forest::new(trees))
     }
 }
 
-impl From<Lines<'_>> for Forest {
-    fn from(input: Lines<'_>) -> Self {
-        let trees = input
-            .map(|line| line.chars().map(Tree::from).collect())
-            .collect();
-        Forest { trees }
+#[cfg(test)]
+mod tests {
+    use crate::forest::Forest;
+    use crate::forest_error::ForestError;
+
+    const EXAMPLE: &str = "30373\n25512\n65332\n33549\n35390";
+
+    #[test]
+    fn visibility() {
+        let forest = Forest::try_from(EXAMPLE.lines()).unwrap();
+        let count: usize = forest
+            .visibility()
+            .iter()
+            .map(|row| row.iter().filter(|v| **v).count())
+            .sum();
+        assert_eq!(count, 21);
+        assert!(forest.is_visible(&(1, 1)));
+        assert!(!forest.is_visible(&(2, 2)));
+    }
+
+    #[test]
+    fn scenic_scores() {
+        let forest = Forest::try_from(EXAMPLE.lines()).unwrap();
+        assert_eq!(forest.get_scenic_score(&(2, 1)), 4);
+        assert_eq!(forest.get_scenic_score(&(2, 3)), 8);
+        assert_eq!(forest.get_scenic_score(&(0, 2)), 0);
+        assert_eq!(forest.scenic_scores()[3], vec![0, 1, 8, 3, 0]);
+    }
+
+    #[test]
+    fn holes() {
+        let forest = Forest::try_from("55555\n5.7.5\n55555".lines()).unwrap();
+        assert_eq!(forest.get(&(1, 1)), None);
+        assert!(!forest.is_visible(&(1, 1)));
+        assert_eq!(forest.scenic_scores()[1], vec![0, 0, 4, 0, 0]);
+        assert!(forest.is_visible(&(2, 1)));
+        assert_eq!(forest.iter_trees().count(), 13);
+    }
+
+    #[test]
+    fn ragged_rows() {
+        let forest = Forest::try_from("12\n3456\n7".lines()).unwrap();
+        assert_eq!(forest.width(), 4);
+        assert_eq!(forest.get(&(1, 2)), None);
+        assert!(forest.is_visible(&(1, 1)));
+        assert!(forest.is_visible(&(3, 1)));
+        assert_eq!(forest.scenic_scores()[1], vec![0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn comma_separated_heights() {
+        let forest = Forest::try_from("10,2,.\n3,12,4\n1,0,2".lines()).unwrap();
+        assert_eq!(forest.get(&(2, 0)), None);
+        assert_eq!(forest.get(&(1, 1)).map(|t| t.height()), Some(12));
+        assert!(forest.is_visible(&(1, 1)));
+        assert_eq!(forest.get_scenic_score(&(1, 1)), 1);
+        assert_eq!(forest.best_tree(), Some((1, 1)));
+    }
+
+    #[test]
+    fn invalid_heights() {
+        let error = ForestError {
+            line: 2,
+            column: 2,
+            value: "x".to_string(),
+        };
+        assert_eq!(Forest::try_from("12\n3x".lines()).err(), Some(error));
+        let error = ForestError {
+            line: 1,
+            column: 2,
+            value: "a".to_string(),
+        };
+        assert_eq!(Forest::try_from("1, a".lines()).err(), Some(error));
     }
 }
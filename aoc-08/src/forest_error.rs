@@ -0,0 +1,20 @@
+use std::fmt::{Display, Formatter};
+
+/// A cell of the puzzle input that is neither a height nor a hole. Lines and columns start at 1,
+/// where columns count characters, or fields in the comma-separated format.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ForestError {
+    pub line: usize,
+    pub column: usize,
+    pub value: String,
+}
+
+impl Display for ForestError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "line {}: invalid height {:?} at column {}",
+            self.line, self.value, self.column
+        )
+    }
+}
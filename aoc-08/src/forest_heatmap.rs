@@ -0,0 +1,220 @@
+use crate::forest::Forest;
+use std::fmt::Write;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum HeatmapLayer {
+    Heights,
+    Visibility,
+    ScenicScores,
+}
+
+impl TryFrom<&str> for HeatmapLayer {
+    type Error = String;
+
+    fn try_from(input: &str) -> Result<Self, Self::Error> {
+        match input {
+            "heights" => Ok(HeatmapLayer::Heights),
+            "visibility" => Ok(HeatmapLayer::Visibility),
+            "scenic" => Ok(HeatmapLayer::ScenicScores),
+            _ => Err(format!("unknown heatmap layer: {}", input)),
+        }
+    }
+}
+
+/// Draws one value per tree as an image. PGM and PPM are grayscale, where brighter is higher, and
+/// the ANSI output runs from blue for the lowest value to red for the highest. PPM highlights the
+/// tree with the best scenic score in red and ANSI in white. Holes and the space past the end of
+/// short rows are dark blue in PPM and black in PGM and ANSI.
+pub struct ForestHeatmap {
+    /// Each value scaled to 0-255, or `None` where there's no tree, in rows padded to the same
+    /// length.
+    levels: Vec<Vec<Option<u8>>>,
+    best: Option<(usize, usize)>,
+}
+
+impl ForestHeatmap {
+    pub fn new(forest: &Forest, layer: HeatmapLayer) -> Self {
+        let values: Vec<Vec<Option<usize>>> = match layer {
+            HeatmapLayer::Heights => forest
+                .rows()
+                .iter()
+                .map(|row| row.iter().map(|t| t.map(|t| t.height() as usize)).collect())
+                .collect(),
+            HeatmapLayer::Visibility => forest
+                .visibility()
+                .iter()
+                .map(|row| row.iter().map(|v| Some(*v as usize)).collect())
+                .collect(),
+            HeatmapLayer::ScenicScores => forest
+                .scenic_scores()
+                .iter()
+                .map(|row| row.iter().copied().map(Some).collect())
+                .collect(),
+        };
+        let max = values.iter().flatten().flatten().max().copied();
+        let levels = values
+            .iter()
+            .enumerate()
+            .map(|(y, row)| {
+                let mut row: Vec<Option<u8>> = row
+                    .iter()
+                    .enumerate()
+                    .map(|(x, v)| match (forest.get(&(x, y)), v, max) {
+                        (Some(_), Some(v), Some(max)) if max > 0 => Some((v * 255 / max) as u8),
+                        (Some(_), Some(_), _) => Some(0),
+                        _ => None,
+                    })
+                    .collect();
+                row.resize(forest.width(), None);
+                row
+            })
+            .collect();
+        ForestHeatmap {
+            levels,
+            best: forest.best_tree(),
+        }
+    }
+
+    /// A plain (P2) grayscale PGM image, one pixel per tree.
+    pub fn to_pgm(&self) -> String {
+        let mut output = self.header("P2");
+        for row in &self.levels {
+            let line: Vec<String> = row
+                .iter()
+                .map(|level| level.unwrap_or_default().to_string())
+                .collect();
+            writeln!(output, "{}", line.join(" ")).unwrap();
+        }
+        output
+    }
+
+    /// A plain (P3) color PPM image, one pixel per tree.
+    pub fn to_ppm(&self) -> String {
+        let mut output = self.header("P3");
+        for (y, row) in self.levels.iter().enumerate() {
+            let line: Vec<String> = row
+                .iter()
+                .enumerate()
+                .map(|(x, level)| match level {
+                    _ if self.best == Some((x, y)) => "255 0 0".to_string(),
+                    Some(level) => format!("{} {} {}", level, level, level),
+                    None => "0 0 96".to_string(),
+                })
+                .collect();
+            writeln!(output, "{}", line.join(" ")).unwrap();
+        }
+        output
+    }
+
+    /// Two spaces per tree with a 24-bit color background, for printing to a terminal.
+    pub fn to_ansi(&self) -> String {
+        let mut output = String::new();
+        for (y, row) in self.levels.iter().enumerate() {
+            for (x, level) in row.iter().enumerate() {
+                let (r, g, b) = match level {
+                    _ if self.best == Some((x, y)) => (255, 255, 255),
+                    Some(level) => gradient(*level),
+                    None => (0, 0, 0),
+                };
+                write!(output, "\x1b[48;2;{};{};{}m  ", r, g, b).unwrap();
+            }
+            writeln!(output, "\x1b[0m").unwrap();
+        }
+        output
+    }
+
+    fn header(&self, magic: &str) -> String {
+        let width = self.levels.first().map_or(0, Vec::len);
+        format!("{}\n{} {}\n255\n", magic, width, self.levels.len())
+    }
+}
+
+/// The colors that levels 0 to 255 run through, evenly spaced: blue, cyan, green, yellow, red.
+const GRADIENT: [(u8, u8, u8); 5] = [
+    (0, 0, 255),
+    (0, 255, 255),
+    (0, 255, 0),
+    (255, 255, 0),
+    (255, 0, 0),
+];
+
+/// The color for `level`, blended between the two nearest colors of the [`GRADIENT`].
+fn gradient(level: u8) -> (u8, u8, u8) {
+    let position = level as usize * (GRADIENT.len() - 1);
+    let i = (position / 255).min(GRADIENT.len() - 2);
+    let t = (position - i * 255) as isize;
+    let blend = |from: u8, to: u8| (from as isize + (to as isize - from as isize) * t / 255) as u8;
+    let (from, to) = (GRADIENT[i], GRADIENT[i + 1]);
+    (
+        blend(from.0, to.0),
+        blend(from.1, to.1),
+        blend(from.2, to.2),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::forest::Forest;
+    use crate::forest_heatmap::{gradient, ForestHeatmap, HeatmapLayer};
+
+    const EXAMPLE: &str = "30373\n25512\n65332\n33549\n35390";
+
+    #[test]
+    fn pgm_of_heights() {
+        let forest = Forest::try_from("09\n90".lines()).unwrap();
+        let heatmap = ForestHeatmap::new(&forest, HeatmapLayer::Heights);
+        assert_eq!(heatmap.to_pgm(), "P2\n2 2\n255\n0 255\n255 0\n");
+    }
+
+    #[test]
+    fn ppm_highlights_best_tree() {
+        let forest = Forest::try_from(EXAMPLE.lines()).unwrap();
+        let heatmap = ForestHeatmap::new(&forest, HeatmapLayer::ScenicScores);
+        let ppm = heatmap.to_ppm();
+        let rows: Vec<&str> = ppm.lines().collect();
+        assert_eq!(rows[..3], ["P3", "5 5", "255"]);
+        assert_eq!(rows[6], "0 0 0 31 31 31 255 0 0 95 95 95 0 0 0");
+    }
+
+    #[test]
+    fn ansi_of_visibility() {
+        let forest = Forest::try_from("111\n121\n111".lines()).unwrap();
+        let heatmap = ForestHeatmap::new(&forest, HeatmapLayer::Visibility);
+        let ansi = heatmap.to_ansi();
+        let rows: Vec<&str> = ansi.lines().collect();
+        assert_eq!(rows.len(), 3);
+        assert_eq!(
+            rows[1],
+            "\x1b[48;2;255;0;0m  \x1b[48;2;255;255;255m  \x1b[48;2;255;0;0m  \x1b[0m"
+        );
+    }
+
+    #[test]
+    fn gradient_runs_blue_to_red() {
+        assert_eq!(gradient(0), (0, 0, 255));
+        assert_eq!(gradient(102), (0, 255, 102));
+        assert_eq!(gradient(204), (255, 204, 0));
+        assert_eq!(gradient(255), (255, 0, 0));
+    }
+
+    #[test]
+    fn parses_layers() {
+        assert_eq!(
+            HeatmapLayer::try_from("scenic"),
+            Ok(HeatmapLayer::ScenicScores)
+        );
+        assert!(HeatmapLayer::try_from("color").is_err());
+    }
+
+    #[test]
+    fn pads_ragged_rows() {
+        let forest = Forest::try_from("12\n3.5\n6".lines()).unwrap();
+        let heatmap = ForestHeatmap::new(&forest, HeatmapLayer::Heights);
+        assert_eq!(
+            heatmap.to_pgm(),
+            "P2\n3 3\n255\n42 85 0\n127 0 212\n255 0 0\n"
+        );
+        let ppm = heatmap.to_ppm();
+        assert_eq!(ppm.lines().nth(4), Some("127 127 127 0 0 96 212 212 212"));
+    }
+}
@@ -1,12 +1,15 @@
 mod forest;
+mod forest_error;
+mod forest_heatmap;
 mod tree;
 
 use crate::forest::Forest;
+use crate::forest_heatmap::{ForestHeatmap, HeatmapLayer};
 use aoc_core::puzzle_input::PuzzleInput;
 
 fn main() {
     let input = PuzzleInput::default();
-    let forest = Forest::from(input.to_lines());
+    let forest = Forest::try_from(input.to_lines()).unwrap();
 
     let visible_trees = forest
         .iter_trees()
@@ -20,4 +23,16 @@ fn main() {
         .max()
         .unwrap();
     println!("Highest scenic score possible: {}", highest_scenic_score);
+
+    // Set AOC_08_HEATMAP to heights, visibility or scenic to draw that layer in the terminal, and
+    // AOC_08_HEATMAP_FILE to a path ending in .pgm or .ppm to save it as an image instead.
+    if let Ok(layer) = std::env::var("AOC_08_HEATMAP") {
+        let heatmap = ForestHeatmap::new(&forest, HeatmapLayer::try_from(layer.as_str()).unwrap());
+        match std::env::var("AOC_08_HEATMAP_FILE") {
+            Ok(path) if path.ends_with(".pgm") => std::fs::write(path, heatmap.to_pgm()).unwrap(),
+            Ok(path) if path.ends_with(".ppm") => std::fs::write(path, heatmap.to_ppm()).unwrap(),
+            Ok(path) => panic!("Unknown image format: {}", path),
+            Err(_) => print!("{}", heatmap.to_ansi()),
+        }
+    }
 }
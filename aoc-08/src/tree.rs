@@ -1,11 +1,21 @@
+use std::num::ParseIntError;
+
 #[derive(Debug, PartialOrd, PartialEq, Ord, Eq, Copy, Clone)]
 pub struct Tree {
-    height: u8,
+    height: u32,
 }
 
-impl From<char> for Tree {
-    fn from(input: char) -> Self {
-        let height = input.to_digit(10).unwrap() as u8;
-        Tree { height }
+impl Tree {
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+}
+
+impl TryFrom<&str> for Tree {
+    type Error = ParseIntError;
+
+    fn try_from(input: &str) -> Result<Self, Self::Error> {
+        let height = input.trim().parse()?;
+        Ok(Tree { height })
     }
 }
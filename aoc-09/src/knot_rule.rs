@@ -0,0 +1,61 @@
+use crate::rope_grid::RopePoint;
+use std::array::from_fn;
+
+/// Describes how a knot follows the knot ahead of it, in `N` dimensions.
+pub trait KnotRule<const N: usize> {
+    /// Returns where a knot at `knot` moves to, given the latest positions of the knot ahead of
+    /// it, oldest first and ending with where it has just moved. At least [`KnotRule::history`]
+    /// positions are given, unless the knot ahead hasn't been in that many yet.
+    fn follow(&self, leader: &[RopePoint<N>], knot: RopePoint<N>) -> RopePoint<N>;
+
+    /// How many of the latest positions of the knot ahead `follow` looks at.
+    fn history(&self) -> usize {
+        1
+    }
+}
+
+/// A knot stays put while it is within `slack` cells of the knot ahead along every axis, which is
+/// the Chebyshev distance, and otherwise takes one step towards it along every axis where they
+/// differ. A slack of 1, the default, is how the puzzle's rope moves.
+pub struct SlackRule {
+    pub slack: isize,
+}
+
+impl<const N: usize> KnotRule<N> for SlackRule {
+    fn follow(&self, leader: &[RopePoint<N>], knot: RopePoint<N>) -> RopePoint<N> {
+        let head = leader.last().unwrap();
+        let dist: RopePoint<N> = from_fn(|i| head[i] - knot[i]);
+        if dist.iter().all(|d| d.abs() <= self.slack) {
+            return knot;
+        }
+        from_fn(|i| knot[i] + dist[i].signum())
+    }
+}
+
+impl Default for SlackRule {
+    fn default() -> Self {
+        SlackRule { slack: 1 }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::knot_rule::{KnotRule, SlackRule};
+
+    #[test]
+    fn follows_when_two_cells_away() {
+        let rule = SlackRule::default();
+        assert_eq!(rule.follow(&[[1, 1]], [0, 0]), [0, 0]);
+        assert_eq!(rule.follow(&[[2, 0]], [0, 0]), [1, 0]);
+        assert_eq!(rule.follow(&[[2, 1]], [0, 0]), [1, 1]);
+        assert_eq!(rule.follow(&[[-2, -2]], [0, 0]), [-1, -1]);
+        assert_eq!(rule.follow(&[[1, -1, 2]], [0, 0, 0]), [1, -1, 1]);
+    }
+
+    #[test]
+    fn follows_with_slack() {
+        let rule = SlackRule { slack: 2 };
+        assert_eq!(rule.follow(&[[2, 1]], [0, 0]), [0, 0]);
+        assert_eq!(rule.follow(&[[3, 1]], [0, 0]), [1, 1]);
+    }
+}
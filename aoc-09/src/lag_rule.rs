@@ -0,0 +1,33 @@
+use crate::knot_rule::KnotRule;
+use crate::rope_grid::RopePoint;
+
+/// A knot that moves to wherever the knot ahead was `steps` steps ago, like the body of a snake.
+/// Until the knot ahead has moved that many times, it stays where the knot ahead started.
+pub struct LagRule {
+    pub steps: usize,
+}
+
+impl<const N: usize> KnotRule<N> for LagRule {
+    fn follow(&self, leader: &[RopePoint<N>], _knot: RopePoint<N>) -> RopePoint<N> {
+        leader[leader.len().saturating_sub(self.steps + 1)]
+    }
+
+    fn history(&self) -> usize {
+        self.steps + 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::knot_rule::KnotRule;
+    use crate::lag_rule::LagRule;
+
+    #[test]
+    fn lags_behind() {
+        let leader = [[0, 0], [1, 0], [2, 0], [2, 1]];
+        assert_eq!(LagRule { steps: 1 }.follow(&leader, [0, 0]), [2, 0]);
+        assert_eq!(LagRule { steps: 3 }.follow(&leader, [0, 0]), [0, 0]);
+        assert_eq!(LagRule { steps: 9 }.follow(&leader, [0, 0]), [0, 0]);
+        assert_eq!(LagRule { steps: 0 }.follow(&leader, [0, 0]), [2, 1]);
+    }
+}
@@ -1,6 +1,14 @@
+mod knot_rule;
+mod lag_rule;
+mod orthogonal_rule;
+mod rope_error;
 mod rope_grid;
 mod rope_movement;
+mod rope_renderer;
 
+use crate::knot_rule::{KnotRule, SlackRule};
+use crate::lag_rule::LagRule;
+use crate::orthogonal_rule::OrthogonalRule;
 use crate::rope_grid::RopeGrid;
 use crate::rope_movement::RopeMovement;
 use aoc_core::puzzle_input::PuzzleInput;
@@ -8,23 +16,62 @@ use aoc_core::puzzle_input::PuzzleInput;
 fn main() {
     let input = PuzzleInput::default();
     let movements: Vec<RopeMovement> = input.to_lines().map(RopeMovement::from).collect();
-    let mut rope = RopeGrid::default();
+    let mut rope: RopeGrid = RopeGrid::default();
     for movement in &movements {
-        rope.move_head(movement);
+        rope.move_head(movement).unwrap_or_else(|e| panic!("{}", e));
     }
-    let unique_tail_points = rope.tail_visited.len();
+    let unique_tail_points = rope.tail_visited().len();
     println!(
         "Size 2 rope tail visited {} unique points",
         unique_tail_points
     );
 
-    let mut rope = RopeGrid::new(10);
+    // Set AOC_09_FRAMES to a path to save a drawing of the rope after every movement, followed
+    // by the cells its tail visited, or to - to print them instead.
+    let frames = std::env::var("AOC_09_FRAMES").ok();
+    let mut drawing = String::new();
+    let mut rope: RopeGrid = RopeGrid::new(10);
     for movement in &movements {
-        rope.move_head(movement);
+        rope.move_head(movement).unwrap_or_else(|e| panic!("{}", e));
+        if frames.is_some() {
+            drawing += &format!(
+                "== {} ==\n{}\n",
+                movement,
+                rope_renderer::render_knots(&rope)
+            );
+        }
     }
-    let unique_tail_points = rope.tail_visited.len();
+    if let Some(path) = frames {
+        drawing += &format!(
+            "== Tail ==\n{}",
+            rope_renderer::render_visited(rope.tail_visited())
+        );
+        match path.as_str() {
+            "-" => print!("{}", drawing),
+            _ => std::fs::write(path, drawing).unwrap(),
+        }
+    }
+    let unique_tail_points = rope.tail_visited().len();
     println!(
         "Size 10 rope tail visited {} unique points",
         unique_tail_points
     );
+
+    let rules: [(&str, Box<dyn KnotRule<2>>); 3] = [
+        ("slack 2", Box::new(SlackRule { slack: 2 })),
+        ("orthogonal", Box::new(OrthogonalRule)),
+        ("lag 1", Box::new(LagRule { steps: 1 })),
+    ];
+    for (name, rule) in rules {
+        let mut rope: RopeGrid = RopeGrid::with_rule(10, rule);
+        for movement in &movements {
+            rope.move_head(movement).unwrap_or_else(|e| panic!("{}", e));
+        }
+        println!(
+            "Size 10 rope with {} rule: head visited {}, tail visited {} unique points",
+            name,
+            rope.visited(0).len(),
+            rope.tail_visited().len()
+        );
+    }
 }
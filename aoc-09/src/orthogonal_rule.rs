@@ -0,0 +1,33 @@
+use crate::knot_rule::KnotRule;
+use crate::rope_grid::RopePoint;
+
+/// A knot that can only move along one axis at a time. It stays put while it overlaps or is next
+/// to the knot ahead, and otherwise steps along the axis where the gap is widest, the first such
+/// axis if there's a tie.
+pub struct OrthogonalRule;
+
+impl<const N: usize> KnotRule<N> for OrthogonalRule {
+    fn follow(&self, leader: &[RopePoint<N>], mut knot: RopePoint<N>) -> RopePoint<N> {
+        let head = leader.last().unwrap();
+        let dist: Vec<isize> = (0..N).map(|i| head[i] - knot[i]).collect();
+        if dist.iter().map(|d| d.abs()).sum::<isize>() > 1 {
+            let axis = (0..N).rev().max_by_key(|i| dist[*i].abs()).unwrap();
+            knot[axis] += dist[axis].signum();
+        }
+        knot
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::knot_rule::KnotRule;
+    use crate::orthogonal_rule::OrthogonalRule;
+
+    #[test]
+    fn moves_orthogonally() {
+        assert_eq!(OrthogonalRule.follow(&[[0, 1]], [0, 0]), [0, 0]);
+        assert_eq!(OrthogonalRule.follow(&[[1, 1]], [0, 0]), [1, 0]);
+        assert_eq!(OrthogonalRule.follow(&[[1, -2]], [0, 0]), [0, -1]);
+        assert_eq!(OrthogonalRule.follow(&[[1, 1, 2]], [0, 0, 0]), [0, 0, 1]);
+    }
+}
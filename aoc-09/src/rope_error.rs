@@ -0,0 +1,19 @@
+use crate::rope_movement::RopeDirection;
+use std::fmt::{Display, Formatter};
+
+/// A movement along an axis that the rope doesn't have, such as forward in two dimensions.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct RopeError {
+    pub dir: RopeDirection,
+    pub dimensions: usize,
+}
+
+impl Display for RopeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "cannot move {} in {} dimensions",
+            self.dir, self.dimensions
+        )
+    }
+}
@@ -1,59 +1,146 @@
+use crate::knot_rule::{KnotRule, SlackRule};
+use crate::rope_error::RopeError;
 use crate::rope_movement::RopeMovement;
-use aoc_core::cardinal_direction::CardinalDirection;
 use std::collections::HashSet;
 
-type RopePoint = (isize, isize);
+/// A point with a coordinate for each of `N` axes, x first.
+pub type RopePoint<const N: usize = 2> = [isize; N];
 
-pub struct RopeGrid {
-    rope: Vec<RopePoint>,
-    pub tail_visited: HashSet<RopePoint>,
+/// A rope in `N` dimensions, where every knot starts at the origin.
+pub struct RopeGrid<const N: usize = 2> {
+    rope: Vec<RopePoint<N>>,
+    rule: Box<dyn KnotRule<N>>,
+    /// The latest positions of each knot, oldest first. Only the last `history` of them are
+    /// needed, and older ones are dropped once there are twice that many.
+    trails: Vec<Vec<RopePoint<N>>>,
+    history: usize,
+    visited: Vec<HashSet<RopePoint<N>>>,
 }
 
-impl RopeGrid {
+impl<const N: usize> RopeGrid<N> {
     pub fn new(size: usize) -> Self {
+        RopeGrid::with_rule(size, Box::new(SlackRule::default()))
+    }
+
+    pub fn with_rule(size: usize, rule: Box<dyn KnotRule<N>>) -> Self {
         if size < 2 {
             panic!("Rope must have at least two knots!");
         }
-        let rope = Vec::from_iter((0..size).map(|_| (0, 0)));
+        let rope = Vec::from_iter((0..size).map(|_| [0; N]));
         RopeGrid {
+            trails: rope.iter().map(|knot| vec![*knot]).collect(),
+            visited: rope.iter().map(|knot| HashSet::from([*knot])).collect(),
+            history: rule.history().max(1),
             rope,
-            tail_visited: HashSet::new(),
+            rule,
         }
     }
 
-    pub fn move_head(&mut self, movement: &RopeMovement) {
+    /// The position of each knot, head first.
+    pub fn knots(&self) -> &[RopePoint<N>] {
+        &self.rope
+    }
+
+    /// Every cell that the knot at `index` has been in, where the head is 0.
+    pub fn visited(&self, index: usize) -> &HashSet<RopePoint<N>> {
+        &self.visited[index]
+    }
+
+    pub fn tail_visited(&self) -> &HashSet<RopePoint<N>> {
+        self.visited.last().unwrap()
+    }
+
+    /// Moves the head step by step, with the other knots following. Fails without moving if the
+    /// movement is along an axis past the rope's `N` dimensions.
+    pub fn move_head(&mut self, movement: &RopeMovement) -> Result<(), RopeError> {
+        let (axis, step) = movement.dir.axis();
+        if axis >= N {
+            return Err(RopeError {
+                dir: movement.dir,
+                dimensions: N,
+            });
+        }
         for _ in 0..movement.dist {
-            let head = &mut self.rope[0];
-            match movement.dir {
-                CardinalDirection::North => *head = (head.0, head.1 - 1),
-                CardinalDirection::South => *head = (head.0, head.1 + 1),
-                CardinalDirection::East => *head = (head.0 + 1, head.1),
-                CardinalDirection::West => *head = (head.0 - 1, head.1),
-            };
+            let mut head = self.rope[0];
+            head[axis] += step;
+            self.place_knot(0, head);
             for i in 1..self.rope.len() {
-                self.move_tail(i);
+                let knot = self.rule.follow(&self.trails[i - 1], self.rope[i]);
+                self.place_knot(i, knot);
             }
-            self.tail_visited.insert(*self.rope.last().unwrap());
         }
+        Ok(())
     }
 
-    fn move_tail(&mut self, index: usize) {
-        let head = &self.rope[index - 1];
-        let tail = &self.rope[index];
-        let x_dist = head.0 - tail.0;
-        let y_dist = head.1 - tail.1;
-        if x_dist.abs() == 2 && y_dist.abs() == 2 {
-            self.rope[index] = (tail.0 + x_dist / 2, tail.1 + y_dist / 2)
-        } else if x_dist.abs() == 2 {
-            self.rope[index] = (tail.0 + x_dist / 2, head.1)
-        } else if y_dist.abs() == 2 {
-            self.rope[index] = (head.0, tail.1 + y_dist / 2)
+    fn place_knot(&mut self, index: usize, knot: RopePoint<N>) {
+        self.rope[index] = knot;
+        let trail = &mut self.trails[index];
+        trail.push(knot);
+        if trail.len() >= 2 * self.history {
+            trail.drain(..trail.len() - self.history);
         }
+        self.visited[index].insert(knot);
     }
 }
 
-impl Default for RopeGrid {
+impl<const N: usize> Default for RopeGrid<N> {
     fn default() -> Self {
         RopeGrid::new(2)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::lag_rule::LagRule;
+    use crate::rope_grid::RopeGrid;
+    use crate::rope_movement::RopeMovement;
+
+    const EXAMPLE: [&str; 8] = ["R 4", "U 4", "L 3", "D 1", "R 4", "D 1", "L 5", "R 2"];
+
+    fn run<const N: usize>(mut rope: RopeGrid<N>) -> RopeGrid<N> {
+        for line in EXAMPLE {
+            rope.move_head(&RopeMovement::from(line)).unwrap();
+        }
+        rope
+    }
+
+    #[test]
+    fn visits_of_every_knot() {
+        let rope: RopeGrid = run(RopeGrid::new(10));
+        assert_eq!(rope.visited(0).len(), 21);
+        assert_eq!(rope.visited(1).len(), 13);
+        assert_eq!(rope.tail_visited().len(), 1);
+        assert_eq!(run(RopeGrid::<2>::default()).tail_visited().len(), 13);
+    }
+
+    #[test]
+    fn alternative_rule() {
+        let rope: RopeGrid = run(RopeGrid::with_rule(3, Box::new(LagRule { steps: 1 })));
+        assert_eq!(rope.visited(1), rope.visited(0));
+        assert!(rope.tail_visited().is_subset(rope.visited(0)));
+
+        let rope: RopeGrid = run(RopeGrid::with_rule(3, Box::new(LagRule { steps: 4 })));
+        assert!(rope.trails.iter().all(|trail| trail.len() < 10));
+        assert_eq!(rope.knots(), [[2, -2], [2, -2], [5, -3]]);
+    }
+
+    #[test]
+    fn three_dimensions() {
+        assert_eq!(run(RopeGrid::<3>::new(10)).tail_visited().len(), 1);
+
+        let mut rope = RopeGrid::<3>::default();
+        for line in ["F 3", "R 2", "B 1"] {
+            rope.move_head(&RopeMovement::from(line)).unwrap();
+        }
+        assert_eq!(rope.knots(), [[2, 0, 2], [1, 0, 3]]);
+        assert_eq!(rope.tail_visited().len(), 4);
+    }
+
+    #[test]
+    fn no_z_axis_in_two_dimensions() {
+        let mut rope = RopeGrid::<2>::default();
+        let error = rope.move_head(&RopeMovement::from("F 1")).unwrap_err();
+        assert_eq!(error.to_string(), "cannot move F in 2 dimensions");
+        assert_eq!(rope.knots(), [[0, 0], [0, 0]]);
+    }
+}
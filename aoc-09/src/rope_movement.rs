@@ -1,7 +1,55 @@
 use aoc_core::cardinal_direction::CardinalDirection;
+use std::fmt::{Display, Formatter};
+
+/// A direction in the plane, where north is up, or forward and backward along the z axis.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum RopeDirection {
+    Planar(CardinalDirection),
+    Forward,
+    Backward,
+}
+
+impl RopeDirection {
+    /// The axis this direction moves along, where x is 0, y is 1 and z is 2, and which way along
+    /// it. North is towards negative y.
+    pub fn axis(&self) -> (usize, isize) {
+        match self {
+            RopeDirection::Planar(CardinalDirection::North) => (1, -1),
+            RopeDirection::Planar(CardinalDirection::South) => (1, 1),
+            RopeDirection::Planar(CardinalDirection::East) => (0, 1),
+            RopeDirection::Planar(CardinalDirection::West) => (0, -1),
+            RopeDirection::Forward => (2, 1),
+            RopeDirection::Backward => (2, -1),
+        }
+    }
+}
+
+impl From<char> for RopeDirection {
+    fn from(input: char) -> Self {
+        match input.to_ascii_lowercase() {
+            'f' => RopeDirection::Forward,
+            'b' => RopeDirection::Backward,
+            c => RopeDirection::Planar(c.into()),
+        }
+    }
+}
+
+impl Display for RopeDirection {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let dir = match self {
+            RopeDirection::Planar(CardinalDirection::North) => 'U',
+            RopeDirection::Planar(CardinalDirection::South) => 'D',
+            RopeDirection::Planar(CardinalDirection::East) => 'R',
+            RopeDirection::Planar(CardinalDirection::West) => 'L',
+            RopeDirection::Forward => 'F',
+            RopeDirection::Backward => 'B',
+        };
+        write!(f, "{}", dir)
+    }
+}
 
 pub struct RopeMovement {
-    pub dir: CardinalDirection,
+    pub dir: RopeDirection,
     pub dist: usize,
 }
 
@@ -13,3 +61,9 @@ impl From<&str> for RopeMovement {
         RopeMovement { dir, dist }
     }
 }
+
+impl Display for RopeMovement {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", self.dir, self.dist)
+    }
+}
@@ -0,0 +1,96 @@
+use crate::rope_grid::{RopeGrid, RopePoint};
+use std::collections::HashSet;
+
+/// Draws every knot of `rope`, in a viewport just big enough for the knots and the starting
+/// point. The head is `H`, the tail is `T` and the knots between are numbered from 1, with earlier
+/// knots drawn over later ones. The starting point is `s` when no knot covers it.
+pub fn render_knots(rope: &RopeGrid) -> String {
+    let knots = rope.knots();
+    let label = |index: usize| match index {
+        0 => 'H',
+        i if i == knots.len() - 1 => 'T',
+        i => char::from_digit(i as u32 % 36, 36).unwrap(),
+    };
+    draw(knots.iter().chain([&[0, 0]]), |point| {
+        knots
+            .iter()
+            .position(|knot| *knot == point)
+            .map(label)
+            .unwrap_or(if point == [0, 0] { 's' } else { '.' })
+    })
+}
+
+/// Draws the cells in `visited` as `#`, apart from the starting point which is `s`.
+pub fn render_visited(visited: &HashSet<RopePoint>) -> String {
+    draw(visited.iter().chain([&[0, 0]]), |point| match point {
+        [0, 0] => 's',
+        p if visited.contains(&p) => '#',
+        _ => '.',
+    })
+}
+
+/// Draws the smallest rectangle containing `points`, with north at the top.
+fn draw<'a, I, F>(points: I, cell: F) -> String
+where
+    I: Iterator<Item = &'a RopePoint> + Clone,
+    F: Fn(RopePoint) -> char,
+{
+    let min_x = points.clone().map(|p| p[0]).min().unwrap_or_default();
+    let max_x = points.clone().map(|p| p[0]).max().unwrap_or_default();
+    let min_y = points.clone().map(|p| p[1]).min().unwrap_or_default();
+    let max_y = points.map(|p| p[1]).max().unwrap_or_default();
+    let mut output = String::new();
+    for y in min_y..=max_y {
+        output.extend((min_x..=max_x).map(|x| cell([x, y])));
+        output.push('\n');
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::rope_grid::RopeGrid;
+    use crate::rope_movement::RopeMovement;
+    use crate::rope_renderer::{render_knots, render_visited};
+
+    #[test]
+    fn renders_knots() {
+        let mut rope: RopeGrid = RopeGrid::new(10);
+        rope.move_head(&RopeMovement::from("R 4")).unwrap();
+        assert_eq!(render_knots(&rope), "4321H\n");
+        rope.move_head(&RopeMovement::from("U 4")).unwrap();
+        let expected = concat!(
+            "....H\n", //
+            "....1\n", //
+            "..432\n", //
+            ".5...\n", //
+            "6....\n",
+        );
+        assert_eq!(render_knots(&rope), expected);
+    }
+
+    #[test]
+    fn renders_short_rope() {
+        let mut rope: RopeGrid = RopeGrid::default();
+        rope.move_head(&RopeMovement::from("L 3")).unwrap();
+        assert_eq!(render_knots(&rope), "HT.s\n");
+        rope.move_head(&RopeMovement::from("D 1")).unwrap();
+        assert_eq!(render_knots(&rope), ".T.s\nH...\n");
+    }
+
+    #[test]
+    fn renders_visited() {
+        let mut rope: RopeGrid = RopeGrid::default();
+        for line in ["R 4", "U 4", "L 3", "D 1", "R 4", "D 1", "L 5", "R 2"] {
+            rope.move_head(&RopeMovement::from(line)).unwrap();
+        }
+        let expected = concat!(
+            "..##.\n", //
+            "...##\n", //
+            ".####\n", //
+            "....#\n", //
+            "s###.\n",
+        );
+        assert_eq!(render_visited(rope.tail_visited()), expected);
+    }
+}
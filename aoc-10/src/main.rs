@@ -1,25 +1,66 @@
 use crate::tube_computer::TubeComputer;
-use crate::tube_instruction::TubeInstruction;
+use crate::tube_debugger::TubeDebugger;
+use crate::tube_display::{TubeDisplay, TubeOverflow};
+use crate::tube_register::TubeRegister;
 use aoc_core::puzzle_input::PuzzleInput;
 
+mod tube_assembler;
+mod tube_assembly_error;
+mod tube_breakpoint;
 mod tube_computer;
+mod tube_debugger;
+mod tube_display;
 mod tube_instruction;
+mod tube_ocr;
+mod tube_register;
+mod tube_repl;
 
 const CHECKPOINTS: [usize; 6] = [20, 60, 100, 140, 180, 220];
 
 fn main() {
     let input = PuzzleInput::default();
-    let instructions: Vec<TubeInstruction> = input.to_lines().map(TubeInstruction::from).collect();
-    let mut cpu = TubeComputer::default();
-    let debug_result: isize = cpu
-        .run(instructions.iter(), |state| {
-            match CHECKPOINTS.contains(&state.cycle) {
-                true => Some(state.cycle as isize * state.acc),
-                false => None,
+    let program = match tube_assembler::assemble(input.as_string()) {
+        Ok(program) => program,
+        Err(errors) => {
+            for error in errors {
+                eprintln!("{}", error);
             }
+            panic!("Program has errors!");
+        }
+    };
+    // Set AOC_10_CRT to width,height,sprite width,wrap or ignore to simulate a different display.
+    let display = || match std::env::var("AOC_10_CRT") {
+        Ok(config) => parse_display(&config).expect("Invalid display configuration!"),
+        Err(_) => TubeDisplay::default(),
+    };
+    // Set AOC_10_DEBUG to step through the program interactively first.
+    if std::env::var("AOC_10_DEBUG").is_ok() {
+        let cpu = TubeComputer::new(display());
+        let mut debugger = TubeDebugger::new(cpu, program.clone());
+        tube_repl::repl(&mut debugger, std::io::stdin().lock(), std::io::stdout()).unwrap();
+    }
+
+    let mut cpu = TubeComputer::new(display());
+    let debug_result: isize = cpu
+        .run(&program, |state| match CHECKPOINTS.contains(&state.cycle) {
+            true => Some(state.cycle as isize * state.register(TubeRegister::X)),
+            false => None,
         })
         .iter()
         .sum();
     println!("Sum of checkpoint signal strengths: {}", debug_result);
     print!("{}", cpu);
+    print!("{}", cpu.display.to_blocks());
+    println!(
+        "Letters on screen: {}",
+        tube_ocr::read_screen(cpu.display.rows())
+    );
+}
+
+fn parse_display(config: &str) -> Option<TubeDisplay> {
+    let mut split = config.split(',');
+    let mut size = || split.next()?.trim().parse::<usize>().ok();
+    let (width, height, sprite_width) = (size()?, size()?, size()?);
+    let overflow = TubeOverflow::try_from(split.next()?.trim()).ok()?;
+    Some(TubeDisplay::new(width, height, sprite_width, overflow))
 }
@@ -0,0 +1,193 @@
+use crate::tube_assembly_error::TubeAssemblyError;
+use crate::tube_instruction::TubeInstruction;
+use crate::tube_register::TubeRegister;
+use std::collections::HashMap;
+
+/// Assembles a program with one instruction per line. A line may start with a label like
+/// `loop:`, which jumps can use as their target, and anything after a `;` is a comment.
+///
+/// The first pass finds the instruction each label points to, so that the second pass can
+/// resolve jumps to labels defined further down. Returns every error found, in line order.
+pub fn assemble(source: &str) -> Result<Vec<TubeInstruction>, Vec<TubeAssemblyError>> {
+    let mut errors = Vec::new();
+    let mut labels: HashMap<&str, usize> = HashMap::new();
+    let mut statements: Vec<(usize, &str)> = Vec::new();
+    for (i, line) in source.lines().enumerate() {
+        let (label, statement) = split_line(line);
+        if let Some(label) = label {
+            if labels.insert(label, statements.len()).is_some() {
+                errors.push(TubeAssemblyError::DuplicateLabel {
+                    line: i + 1,
+                    label: label.to_string(),
+                });
+            }
+        }
+        if !statement.is_empty() {
+            statements.push((i + 1, statement));
+        }
+    }
+
+    let mut program = Vec::new();
+    for (line, statement) in statements {
+        match parse_instruction(line, statement, &labels) {
+            Ok(instruction) => program.push(instruction),
+            Err(error) => errors.push(error),
+        }
+    }
+    match errors.is_empty() {
+        true => Ok(program),
+        false => {
+            errors.sort_by_key(line_of);
+            Err(errors)
+        }
+    }
+}
+
+/// Splits a line into its label, if it has one, and its instruction without the comment.
+fn split_line(line: &str) -> (Option<&str>, &str) {
+    let code = line.split(';').next().unwrap_or_default().trim();
+    match code.split_once(':') {
+        Some((label, rest)) if !label.is_empty() && !label.contains(char::is_whitespace) => {
+            (Some(label), rest.trim())
+        }
+        _ => (None, code),
+    }
+}
+
+fn parse_instruction(
+    line: usize,
+    statement: &str,
+    labels: &HashMap<&str, usize>,
+) -> Result<TubeInstruction, TubeAssemblyError> {
+    let mut split = statement.split_whitespace();
+    let mnemonic = split.next().unwrap_or_default();
+    let mut operand = || {
+        split.next().ok_or(TubeAssemblyError::MissingOperand {
+            line,
+            mnemonic: mnemonic.to_string(),
+        })
+    };
+    let register = |operand: &str| {
+        TubeRegister::try_from(operand).map_err(|_| TubeAssemblyError::InvalidRegister {
+            line,
+            operand: operand.to_string(),
+        })
+    };
+    let value = |operand: &str| {
+        operand
+            .parse::<isize>()
+            .map_err(|_| TubeAssemblyError::InvalidValue {
+                line,
+                operand: operand.to_string(),
+            })
+    };
+    let target = |operand: &str| {
+        labels
+            .get(operand)
+            .copied()
+            .ok_or(TubeAssemblyError::UnknownLabel {
+                line,
+                label: operand.to_string(),
+            })
+    };
+
+    let instruction = match mnemonic {
+        "noop" => TubeInstruction::NoOp,
+        "addx" => TubeInstruction::Add(TubeRegister::X, value(operand()?)?),
+        "addy" => TubeInstruction::Add(TubeRegister::Y, value(operand()?)?),
+        "add" => TubeInstruction::Add(register(operand()?)?, value(operand()?)?),
+        "mul" => TubeInstruction::Mul(register(operand()?)?, value(operand()?)?),
+        "jmp" => TubeInstruction::Jump(target(operand()?)?),
+        "jnz" => TubeInstruction::JumpNotZero(register(operand()?)?, target(operand()?)?),
+        _ => {
+            return Err(TubeAssemblyError::UnknownMnemonic {
+                line,
+                mnemonic: mnemonic.to_string(),
+            })
+        }
+    };
+    match split.next() {
+        Some(extra) => Err(TubeAssemblyError::ExtraOperand {
+            line,
+            operand: extra.to_string(),
+        }),
+        None => Ok(instruction),
+    }
+}
+
+fn line_of(error: &TubeAssemblyError) -> usize {
+    match error {
+        TubeAssemblyError::UnknownMnemonic { line, .. }
+        | TubeAssemblyError::MissingOperand { line, .. }
+        | TubeAssemblyError::ExtraOperand { line, .. }
+        | TubeAssemblyError::InvalidRegister { line, .. }
+        | TubeAssemblyError::InvalidValue { line, .. }
+        | TubeAssemblyError::UnknownLabel { line, .. }
+        | TubeAssemblyError::DuplicateLabel { line, .. } => *line,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tube_assembler::assemble;
+    use crate::tube_assembly_error::TubeAssemblyError;
+    use crate::tube_instruction::TubeInstruction;
+    use crate::tube_register::TubeRegister;
+
+    #[test]
+    fn assembles_labels_and_comments() {
+        let source = "; count down\n  addy 3\nloop: addx 2 ; twice\n\nmul x -1\njnz y loop\nend:\n";
+        let program = assemble(source).unwrap();
+        assert_eq!(
+            program,
+            [
+                TubeInstruction::Add(TubeRegister::Y, 3),
+                TubeInstruction::Add(TubeRegister::X, 2),
+                TubeInstruction::Mul(TubeRegister::X, -1),
+                TubeInstruction::JumpNotZero(TubeRegister::Y, 1),
+            ]
+        );
+        assert_eq!(
+            assemble("jmp end\nnoop\nend:"),
+            Ok(vec![TubeInstruction::Jump(2), TubeInstruction::NoOp])
+        );
+    }
+
+    #[test]
+    fn reports_errors_with_line_numbers() {
+        let source = "a: noop\nfoo 1\naddx\nadd z 1\nmul x two\njmp nowhere\na: noop x";
+        assert_eq!(
+            assemble(source),
+            Err(vec![
+                TubeAssemblyError::UnknownMnemonic {
+                    line: 2,
+                    mnemonic: "foo".to_string()
+                },
+                TubeAssemblyError::MissingOperand {
+                    line: 3,
+                    mnemonic: "addx".to_string()
+                },
+                TubeAssemblyError::InvalidRegister {
+                    line: 4,
+                    operand: "z".to_string()
+                },
+                TubeAssemblyError::InvalidValue {
+                    line: 5,
+                    operand: "two".to_string()
+                },
+                TubeAssemblyError::UnknownLabel {
+                    line: 6,
+                    label: "nowhere".to_string()
+                },
+                TubeAssemblyError::DuplicateLabel {
+                    line: 7,
+                    label: "a".to_string()
+                },
+                TubeAssemblyError::ExtraOperand {
+                    line: 7,
+                    operand: "x".to_string()
+                },
+            ])
+        );
+    }
+}
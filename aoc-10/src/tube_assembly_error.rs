@@ -0,0 +1,41 @@
+use std::fmt::{Display, Formatter};
+
+/// A problem found while assembling a program. Line numbers start at 1.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum TubeAssemblyError {
+    UnknownMnemonic { line: usize, mnemonic: String },
+    MissingOperand { line: usize, mnemonic: String },
+    ExtraOperand { line: usize, operand: String },
+    InvalidRegister { line: usize, operand: String },
+    InvalidValue { line: usize, operand: String },
+    UnknownLabel { line: usize, label: String },
+    DuplicateLabel { line: usize, label: String },
+}
+
+impl Display for TubeAssemblyError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TubeAssemblyError::UnknownMnemonic { line, mnemonic } => {
+                write!(f, "line {}: unknown instruction {:?}", line, mnemonic)
+            }
+            TubeAssemblyError::MissingOperand { line, mnemonic } => {
+                write!(f, "line {}: missing operand for {}", line, mnemonic)
+            }
+            TubeAssemblyError::ExtraOperand { line, operand } => {
+                write!(f, "line {}: unexpected operand {:?}", line, operand)
+            }
+            TubeAssemblyError::InvalidRegister { line, operand } => {
+                write!(f, "line {}: invalid register {:?}", line, operand)
+            }
+            TubeAssemblyError::InvalidValue { line, operand } => {
+                write!(f, "line {}: invalid value {:?}", line, operand)
+            }
+            TubeAssemblyError::UnknownLabel { line, label } => {
+                write!(f, "line {}: unknown label {:?}", line, label)
+            }
+            TubeAssemblyError::DuplicateLabel { line, label } => {
+                write!(f, "line {}: label {:?} is already defined", line, label)
+            }
+        }
+    }
+}
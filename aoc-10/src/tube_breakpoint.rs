@@ -0,0 +1,78 @@
+use crate::tube_computer::TubeComputer;
+use crate::tube_register::TubeRegister;
+use std::fmt::{Display, Formatter};
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum TubeComparison {
+    Equal,
+    NotEqual,
+    Less,
+    Greater,
+}
+
+impl TubeComparison {
+    pub fn holds(&self, left: isize, right: isize) -> bool {
+        match self {
+            TubeComparison::Equal => left == right,
+            TubeComparison::NotEqual => left != right,
+            TubeComparison::Less => left < right,
+            TubeComparison::Greater => left > right,
+        }
+    }
+}
+
+impl TryFrom<&str> for TubeComparison {
+    type Error = ();
+
+    fn try_from(input: &str) -> Result<Self, Self::Error> {
+        match input {
+            "==" => Ok(TubeComparison::Equal),
+            "!=" => Ok(TubeComparison::NotEqual),
+            "<" => Ok(TubeComparison::Less),
+            ">" => Ok(TubeComparison::Greater),
+            _ => Err(()),
+        }
+    }
+}
+
+impl Display for TubeComparison {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TubeComparison::Equal => write!(f, "=="),
+            TubeComparison::NotEqual => write!(f, "!="),
+            TubeComparison::Less => write!(f, "<"),
+            TubeComparison::Greater => write!(f, ">"),
+        }
+    }
+}
+
+/// A condition that pauses the debugger during a cycle.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum TubeBreakpoint {
+    /// During the cycle with this number, counting from 1.
+    Cycle(usize),
+    /// During the first cycle of the instruction at this index.
+    Instruction(usize),
+    /// During every cycle where the register compares to the value like this.
+    Register(TubeRegister, TubeComparison, isize),
+}
+
+impl TubeBreakpoint {
+    pub fn is_hit(&self, cpu: &TubeComputer) -> bool {
+        match self {
+            TubeBreakpoint::Cycle(cycle) => cpu.cycle == *cycle,
+            TubeBreakpoint::Instruction(index) => cpu.pc == *index && cpu.is_instruction_start(),
+            TubeBreakpoint::Register(reg, cmp, val) => cmp.holds(cpu.register(*reg), *val),
+        }
+    }
+}
+
+impl Display for TubeBreakpoint {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TubeBreakpoint::Cycle(cycle) => write!(f, "cycle {}", cycle),
+            TubeBreakpoint::Instruction(index) => write!(f, "instruction {}", index),
+            TubeBreakpoint::Register(reg, cmp, val) => write!(f, "{} {} {}", reg, cmp, val),
+        }
+    }
+}
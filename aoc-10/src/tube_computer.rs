@@ -1,64 +1,152 @@
+use crate::tube_display::TubeDisplay;
 use crate::tube_instruction::TubeInstruction;
-use std::fmt::{Display, Formatter, Write};
+use crate::tube_register::TubeRegister;
+use std::fmt::{Display, Formatter};
 
 pub struct TubeComputer {
-    pub acc: isize,
+    registers: [isize; TubeRegister::COUNT],
     pub cycle: usize,
-    screen: [[bool; 40]; 6],
+    /// The index of the instruction being executed.
+    pub pc: usize,
+    /// How many cycles the current instruction has taken so far.
+    elapsed: usize,
+    pub display: TubeDisplay,
 }
 
 impl TubeComputer {
-    pub fn run<'a, T, R, F>(&mut self, instructions: T, debug: F) -> Vec<R>
+    pub fn new(display: TubeDisplay) -> Self {
+        TubeComputer {
+            registers: [1, 0],
+            cycle: 0,
+            pc: 0,
+            elapsed: 0,
+            display,
+        }
+    }
+
+    pub fn register(&self, register: TubeRegister) -> isize {
+        self.registers[register.index()]
+    }
+
+    /// Whether the current cycle is the first one of the instruction at `pc`.
+    pub fn is_instruction_start(&self) -> bool {
+        self.elapsed == 1
+    }
+
+    /// Runs `program` until it jumps or steps past its last instruction, calling `debug` during
+    /// every cycle and collecting what it returns.
+    pub fn run<R, F>(&mut self, program: &[TubeInstruction], debug: F) -> Vec<R>
     where
-        T: Iterator<Item = &'a TubeInstruction>,
         F: Fn(&TubeComputer) -> Option<R>,
     {
         let mut result: Vec<R> = Vec::new();
-        for inst in instructions {
-            for _ in 0..inst.cycles() {
-                let sprite = (self.acc - 1)..=(self.acc + 1);
-                let col = self.cycle % 40;
-                if sprite.contains(&(col as isize)) {
-                    let row = (self.cycle - col) / 40;
-                    self.screen[row][col] = true;
-                }
+        while self.tick(program) {
+            if let Some(i) = debug(self) {
+                result.push(i);
+            }
+        }
+        result
+    }
 
-                self.cycle += 1;
-                if let Some(i) = debug(self) {
-                    result.push(i);
-                }
+    /// Starts the next cycle of `program`, first applying the instruction that the previous
+    /// cycle finished. Afterwards, the computer is in the middle of the cycle. Returns `false`
+    /// without starting a cycle if the program has ended.
+    pub fn tick(&mut self, program: &[TubeInstruction]) -> bool {
+        if let Some(inst) = program.get(self.pc) {
+            if self.elapsed == inst.cycles() {
+                self.execute(inst);
+                self.elapsed = 0;
             }
-            match inst {
-                TubeInstruction::NoOp => {}
-                TubeInstruction::AddX(val) => self.acc += val,
+        }
+        if self.pc >= program.len() {
+            return false;
+        }
+
+        self.display
+            .draw(self.cycle, self.register(TubeRegister::X));
+        self.cycle += 1;
+        self.elapsed += 1;
+        true
+    }
+
+    /// Applies `inst`. Registers are machine words, so additions and multiplications wrap around
+    /// on overflow instead of failing.
+    fn execute(&mut self, inst: &TubeInstruction) {
+        self.pc = match inst {
+            TubeInstruction::NoOp => self.pc + 1,
+            TubeInstruction::Add(reg, val) => {
+                let register = &mut self.registers[reg.index()];
+                *register = register.wrapping_add(*val);
+                self.pc + 1
             }
+            TubeInstruction::Mul(reg, val) => {
+                let register = &mut self.registers[reg.index()];
+                *register = register.wrapping_mul(*val);
+                self.pc + 1
+            }
+            TubeInstruction::Jump(target) => *target,
+            TubeInstruction::JumpNotZero(reg, target) => match self.register(*reg) {
+                0 => self.pc + 1,
+                _ => *target,
+            },
         }
-        result
     }
 }
 
 impl Default for TubeComputer {
     fn default() -> Self {
-        TubeComputer {
-            acc: 1,
-            cycle: 0,
-            screen: [[false; 40]; 6],
-        }
+        TubeComputer::new(TubeDisplay::default())
     }
 }
 
 impl Display for TubeComputer {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        for row in self.screen.iter() {
-            for cell in row {
-                let char = match cell {
-                    true => '#',
-                    false => '.',
-                };
-                f.write_char(char)?;
-            }
-            f.write_char('\n')?;
-        }
-        Ok(())
+        write!(f, "{}", self.display)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tube_assembler::assemble;
+    use crate::tube_computer::TubeComputer;
+    use crate::tube_register::TubeRegister;
+
+    #[test]
+    fn runs_straight_line_program() {
+        let program = assemble("noop\naddx 3\naddx -5").unwrap();
+        let mut cpu = TubeComputer::default();
+        let xs = cpu.run(&program, |state| Some(state.register(TubeRegister::X)));
+        assert_eq!(xs, [1, 1, 1, 4, 4]);
+        assert_eq!(cpu.register(TubeRegister::X), -1);
+        assert_eq!(cpu.cycle, 5);
+    }
+
+    #[test]
+    fn runs_loops() {
+        let source = "addy 3\nloop: addx 2\naddy -1\njnz y loop\nmul x 10";
+        let program = assemble(source).unwrap();
+        let mut cpu = TubeComputer::default();
+        cpu.run(&program, |_| None::<()>);
+        assert_eq!(cpu.register(TubeRegister::X), 70);
+        assert_eq!(cpu.register(TubeRegister::Y), 0);
+        assert_eq!(cpu.cycle, 2 + 3 * 5 + 2);
+    }
+
+    #[test]
+    fn registers_wrap_around() {
+        let source = "addx 3\nmul x 4611686018427387904\naddy 9223372036854775807\naddy 2";
+        let program = assemble(source).unwrap();
+        let mut cpu = TubeComputer::default();
+        cpu.run(&program, |_| None::<()>);
+        assert_eq!(cpu.register(TubeRegister::X), 0);
+        assert_eq!(cpu.register(TubeRegister::Y), isize::MIN + 1);
+    }
+
+    #[test]
+    fn long_programs_stop_drawing() {
+        let program = assemble("addy 300\nloop: addy -1\njnz y loop").unwrap();
+        let mut cpu = TubeComputer::default();
+        cpu.run(&program, |_| None::<()>);
+        assert_eq!(cpu.cycle, 2 + 300 * 3);
     }
 }
@@ -0,0 +1,148 @@
+use crate::tube_breakpoint::TubeBreakpoint;
+use crate::tube_computer::TubeComputer;
+use crate::tube_instruction::TubeInstruction;
+
+/// Why the debugger stopped running.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum TubeStop {
+    /// The breakpoint at this index was hit.
+    Breakpoint(usize),
+    Halted,
+}
+
+/// Runs a program on a computer a little at a time, so that its state can be inspected between
+/// cycles. The computer is always paused in the middle of a cycle, like the `debug` closure of
+/// [TubeComputer::run] sees it.
+pub struct TubeDebugger {
+    pub cpu: TubeComputer,
+    program: Vec<TubeInstruction>,
+    breakpoints: Vec<TubeBreakpoint>,
+}
+
+impl TubeDebugger {
+    /// Debugs `program` on `cpu`, such as one with a different display, from whatever state it is
+    /// in.
+    pub fn new(cpu: TubeComputer, program: Vec<TubeInstruction>) -> Self {
+        TubeDebugger {
+            cpu,
+            program,
+            breakpoints: Vec::new(),
+        }
+    }
+
+    pub fn breakpoints(&self) -> &[TubeBreakpoint] {
+        &self.breakpoints
+    }
+
+    /// Adds a breakpoint, returning its index.
+    pub fn add_breakpoint(&mut self, breakpoint: TubeBreakpoint) -> usize {
+        self.breakpoints.push(breakpoint);
+        self.breakpoints.len() - 1
+    }
+
+    /// Removes the breakpoint at `index`, moving the ones after it down by one.
+    pub fn remove_breakpoint(&mut self, index: usize) -> Option<TubeBreakpoint> {
+        (index < self.breakpoints.len()).then(|| self.breakpoints.remove(index))
+    }
+
+    /// The instruction being executed, or `None` once the program has ended.
+    pub fn current_instruction(&self) -> Option<&TubeInstruction> {
+        self.program.get(self.cpu.pc)
+    }
+
+    pub fn is_halted(&self) -> bool {
+        self.current_instruction().is_none()
+    }
+
+    /// Moves on to the next cycle. Returns `false` if the program has ended.
+    pub fn step_cycle(&mut self) -> bool {
+        self.cpu.tick(&self.program)
+    }
+
+    /// Moves on to the first cycle of the next instruction to execute. Returns `false` if the
+    /// program has ended.
+    pub fn step_instruction(&mut self) -> bool {
+        loop {
+            if !self.step_cycle() {
+                return false;
+            }
+            if self.cpu.is_instruction_start() {
+                return true;
+            }
+        }
+    }
+
+    /// Runs at least one cycle, then keeps going until a breakpoint is hit or the program ends.
+    pub fn resume(&mut self) -> TubeStop {
+        while self.step_cycle() {
+            if let Some(index) = self.breakpoints.iter().position(|b| b.is_hit(&self.cpu)) {
+                return TubeStop::Breakpoint(index);
+            }
+        }
+        TubeStop::Halted
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tube_assembler::assemble;
+    use crate::tube_breakpoint::{TubeBreakpoint, TubeComparison};
+    use crate::tube_computer::TubeComputer;
+    use crate::tube_debugger::{TubeDebugger, TubeStop};
+    use crate::tube_display::{TubeDisplay, TubeOverflow};
+    use crate::tube_register::TubeRegister;
+
+    fn debugger() -> TubeDebugger {
+        let program = assemble("addy 3\nloop: addx 2\naddy -1\njnz y loop\nnoop").unwrap();
+        TubeDebugger::new(TubeComputer::default(), program)
+    }
+
+    #[test]
+    fn steps_by_cycle_and_instruction() {
+        let mut debugger = debugger();
+        assert!(debugger.step_cycle());
+        assert_eq!((debugger.cpu.cycle, debugger.cpu.pc), (1, 0));
+        assert!(debugger.step_instruction());
+        assert_eq!((debugger.cpu.cycle, debugger.cpu.pc), (3, 1));
+        assert_eq!(debugger.cpu.register(TubeRegister::Y), 3);
+        assert!(debugger.step_instruction());
+        assert_eq!((debugger.cpu.cycle, debugger.cpu.pc), (5, 2));
+        assert_eq!(debugger.cpu.register(TubeRegister::X), 3);
+    }
+
+    #[test]
+    fn stops_at_breakpoints() {
+        let mut debugger = debugger();
+        debugger.add_breakpoint(TubeBreakpoint::Cycle(4));
+        debugger.add_breakpoint(TubeBreakpoint::Instruction(3));
+        let condition = TubeBreakpoint::Register(TubeRegister::X, TubeComparison::Greater, 5);
+        debugger.add_breakpoint(condition);
+
+        assert_eq!(debugger.resume(), TubeStop::Breakpoint(0));
+        assert_eq!(debugger.cpu.cycle, 4);
+        assert_eq!(debugger.resume(), TubeStop::Breakpoint(1));
+        assert_eq!(debugger.cpu.cycle, 7);
+        assert_eq!(
+            debugger.remove_breakpoint(1),
+            Some(TubeBreakpoint::Instruction(3))
+        );
+        assert_eq!(debugger.resume(), TubeStop::Breakpoint(1));
+        assert_eq!(debugger.cpu.register(TubeRegister::X), 7);
+        assert_eq!(debugger.remove_breakpoint(1), Some(condition));
+        assert_eq!(debugger.resume(), TubeStop::Halted);
+        assert!(debugger.is_halted());
+        assert!(!debugger.step_cycle());
+    }
+
+    #[test]
+    fn draws_on_given_display() {
+        let program = assemble("noop\naddx 2\nnoop").unwrap();
+        let display = TubeDisplay::new(2, 2, 3, TubeOverflow::Ignore);
+        let mut debugger = TubeDebugger::new(TubeComputer::new(display), program);
+        assert_eq!(debugger.resume(), TubeStop::Halted);
+        assert_eq!(
+            debugger.cpu.display.rows(),
+            [vec![true, true], vec![true, false]]
+        );
+    }
+}
@@ -0,0 +1,176 @@
+use std::fmt::{Display, Formatter, Write};
+
+/// What the display does once the beam has passed the bottom-right pixel.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum TubeOverflow {
+    /// Starts again from the top-left pixel, drawing over what is there.
+    Wrap,
+    /// Stops drawing.
+    Ignore,
+}
+
+impl TryFrom<&str> for TubeOverflow {
+    type Error = ();
+
+    fn try_from(input: &str) -> Result<Self, Self::Error> {
+        match input {
+            "wrap" => Ok(TubeOverflow::Wrap),
+            "ignore" => Ok(TubeOverflow::Ignore),
+            _ => Err(()),
+        }
+    }
+}
+
+/// A CRT that draws one pixel per cycle, left to right and top to bottom. A pixel is lit if the
+/// sprite covers its column when it is drawn.
+pub struct TubeDisplay {
+    width: usize,
+    height: usize,
+    /// How many columns the sprite covers. For an even width, the middle is left of centre.
+    pub sprite_width: usize,
+    pub overflow: TubeOverflow,
+    pixels: Vec<Vec<bool>>,
+}
+
+impl TubeDisplay {
+    pub fn new(width: usize, height: usize, sprite_width: usize, overflow: TubeOverflow) -> Self {
+        TubeDisplay {
+            width,
+            height,
+            sprite_width,
+            overflow,
+            pixels: vec![vec![false; width]; height],
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// The pixels drawn so far, by row, where `true` is lit.
+    pub fn rows(&self) -> &[Vec<bool>] {
+        &self.pixels
+    }
+
+    /// Draws the pixel for `cycle`, counting from 0, with the middle of the sprite at `sprite`.
+    pub fn draw(&mut self, cycle: usize, sprite: isize) {
+        let (width, height) = (self.width(), self.height());
+        if width == 0 || height == 0 {
+            return;
+        }
+        let col = cycle % width;
+        let row = match self.overflow {
+            TubeOverflow::Wrap => cycle / width % height,
+            TubeOverflow::Ignore if cycle / width < height => cycle / width,
+            TubeOverflow::Ignore => return,
+        };
+        let reach = self.sprite_width.saturating_sub(1) as isize;
+        let left = sprite.saturating_sub(reach / 2);
+        let right = left.saturating_add(reach);
+        self.pixels[row][col] = self.sprite_width > 0 && (left..=right).contains(&(col as isize));
+    }
+
+    /// Draws two rows of pixels per line with Unicode half blocks.
+    pub fn to_blocks(&self) -> String {
+        let mut output = String::new();
+        for pair in self.pixels.chunks(2) {
+            for col in 0..self.width() {
+                let top = pair[0][col];
+                let bottom = pair.get(1).is_some_and(|row| row[col]);
+                output.push(match (top, bottom) {
+                    (true, true) => '█',
+                    (true, false) => '▀',
+                    (false, true) => '▄',
+                    (false, false) => ' ',
+                });
+            }
+            output.push('\n');
+        }
+        output
+    }
+}
+
+impl Default for TubeDisplay {
+    fn default() -> Self {
+        TubeDisplay::new(40, 6, 3, TubeOverflow::Ignore)
+    }
+}
+
+impl Display for TubeDisplay {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for row in self.pixels.iter() {
+            for cell in row {
+                let char = match cell {
+                    true => '#',
+                    false => '.',
+                };
+                f.write_char(char)?;
+            }
+            f.write_char('\n')?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tube_display::{TubeDisplay, TubeOverflow};
+
+    #[test]
+    fn draws_with_sprite_width() {
+        let mut display = TubeDisplay::new(6, 1, 2, TubeOverflow::Ignore);
+        for cycle in 0..6 {
+            display.draw(cycle, 2);
+        }
+        assert_eq!(display.to_string(), "..##..\n");
+
+        let mut display = TubeDisplay::new(6, 1, 5, TubeOverflow::Ignore);
+        for cycle in 0..6 {
+            display.draw(cycle, 2);
+        }
+        assert_eq!(display.to_string(), "#####.\n");
+    }
+
+    #[test]
+    fn wraps_or_ignores_overflow() {
+        let mut display = TubeDisplay::new(3, 2, 1, TubeOverflow::Ignore);
+        for cycle in 0..9 {
+            display.draw(cycle, (cycle % 2) as isize);
+        }
+        assert_eq!(display.to_string(), "##.\n...\n");
+
+        display.overflow = TubeOverflow::Wrap;
+        for cycle in 6..9 {
+            display.draw(cycle, 2);
+        }
+        assert_eq!(display.to_string(), "..#\n...\n");
+    }
+
+    #[test]
+    fn sprite_at_the_limits() {
+        let mut display = TubeDisplay::new(3, 1, 3, TubeOverflow::Ignore);
+        display.draw(0, isize::MAX);
+        display.draw(1, isize::MIN);
+        display.draw(2, 1);
+        assert_eq!(display.to_string(), "..#\n");
+        assert_eq!((display.width(), display.height()), (3, 1));
+
+        display.sprite_width = 0;
+        display.draw(2, 2);
+        assert_eq!(display.to_string(), "...\n");
+    }
+
+    #[test]
+    fn draws_blocks() {
+        let mut display = TubeDisplay::new(4, 3, 1, TubeOverflow::Ignore);
+        for (cycle, sprite) in [0, 1, 3, 3, 0, 1, 2, 0, 0, 1, 2, 3].iter().enumerate() {
+            display.draw(cycle, *sprite);
+        }
+        assert_eq!(display.to_string(), "##.#\n###.\n####\n");
+        assert_eq!(display.to_blocks(), "██▄▀\n▀▀▀▀\n");
+    }
+}
@@ -1,24 +1,37 @@
+use crate::tube_register::TubeRegister;
+use std::fmt::{Display, Formatter};
+
+/// An instruction of an assembled program, where jumps hold the index of their target.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
 pub enum TubeInstruction {
     NoOp,
-    AddX(isize),
+    Add(TubeRegister, isize),
+    Mul(TubeRegister, isize),
+    Jump(usize),
+    /// Jumps if the register isn't zero, and otherwise moves on to the next instruction.
+    JumpNotZero(TubeRegister, usize),
 }
 
 impl TubeInstruction {
     pub fn cycles(&self) -> usize {
         match self {
             TubeInstruction::NoOp => 1,
-            TubeInstruction::AddX(_) => 2,
+            TubeInstruction::Add(..) => 2,
+            TubeInstruction::Mul(..) => 2,
+            TubeInstruction::Jump(_) => 1,
+            TubeInstruction::JumpNotZero(..) => 1,
         }
     }
 }
 
-impl From<&str> for TubeInstruction {
-    fn from(input: &str) -> Self {
-        let mut split = input.split(' ');
-        match split.next().unwrap() {
-            "addx" => TubeInstruction::AddX(split.next().unwrap().parse().unwrap()),
-            "noop" => TubeInstruction::NoOp,
-            _a => panic!("Not a valid command: {}", _a),
+impl Display for TubeInstruction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TubeInstruction::NoOp => write!(f, "noop"),
+            TubeInstruction::Add(reg, val) => write!(f, "add{} {}", reg, val),
+            TubeInstruction::Mul(reg, val) => write!(f, "mul {} {}", reg, val),
+            TubeInstruction::Jump(target) => write!(f, "jmp {}", target),
+            TubeInstruction::JumpNotZero(reg, target) => write!(f, "jnz {} {}", reg, target),
         }
     }
 }
@@ -0,0 +1,131 @@
+use std::fmt::{Display, Formatter};
+
+/// The letters the CRT is known to draw, each 4 pixels wide and 6 tall.
+const FONT: [(char, [&str; 6]); 18] = [
+    ('A', [".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+    ('B', ["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+    ('C', [".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+    ('E', ["####", "#...", "###.", "#...", "#...", "####"]),
+    ('F', ["####", "#...", "###.", "#...", "#...", "#..."]),
+    ('G', [".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+    ('H', ["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+    ('I', [".###", "..#.", "..#.", "..#.", "..#.", ".###"]),
+    ('J', ["..##", "...#", "...#", "...#", "#..#", ".##."]),
+    ('K', ["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+    ('L', ["#...", "#...", "#...", "#...", "#...", "####"]),
+    ('O', [".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('P', ["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+    ('R', ["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+    ('S', [".###", "#...", "#...", ".##.", "...#", "###."]),
+    ('U', ["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('Z', ["####", "...#", "..#.", ".#..", "#...", "####"]),
+    (' ', ["....", "....", "....", "....", "....", "...."]),
+];
+
+const CELL_WIDTH: usize = 5;
+const GLYPH_WIDTH: usize = 4;
+const GLYPH_HEIGHT: usize = 6;
+
+/// The text read from a screen.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct TubeText {
+    /// One character per cell, where `?` is a glyph that isn't in the font.
+    pub text: String,
+    /// The index in `text` and the pixels, as rows of `#` and `.`, of each unknown glyph.
+    pub unknown: Vec<(usize, Vec<String>)>,
+}
+
+impl Display for TubeText {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.text)?;
+        for (index, bitmap) in &self.unknown {
+            write!(f, "\nunknown glyph at {}:", index)?;
+            for row in bitmap {
+                write!(f, "\n{}", row)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Reads the letters drawn on the top 6 rows of a screen, which is split into cells 5 pixels
+/// wide with a letter in the left 4 columns of each. Pixels past the edge of the screen are off.
+pub fn read_screen<R: AsRef<[bool]>>(rows: &[R]) -> TubeText {
+    let width = rows
+        .iter()
+        .map(|r| r.as_ref().len())
+        .max()
+        .unwrap_or_default();
+    let pixel = |x: usize, y: usize| {
+        rows.get(y)
+            .and_then(|row| row.as_ref().get(x))
+            .copied()
+            .unwrap_or_default()
+    };
+
+    let mut text = String::new();
+    let mut unknown = Vec::new();
+    for (index, left) in (0..width).step_by(CELL_WIDTH).enumerate() {
+        let bitmap: Vec<String> = (0..GLYPH_HEIGHT)
+            .map(|y| {
+                (left..left + GLYPH_WIDTH)
+                    .map(|x| if pixel(x, y) { '#' } else { '.' })
+                    .collect()
+            })
+            .collect();
+        match FONT.iter().find(|(_, glyph)| bitmap == glyph) {
+            Some((letter, _)) => text.push(*letter),
+            None => {
+                text.push('?');
+                unknown.push((index, bitmap));
+            }
+        }
+    }
+    TubeText { text, unknown }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tube_ocr::read_screen;
+
+    fn screen(drawing: &str) -> Vec<Vec<bool>> {
+        drawing
+            .lines()
+            .map(|line| line.chars().map(|c| c == '#').collect())
+            .collect()
+    }
+
+    #[test]
+    fn reads_letters() {
+        let screen = screen(concat!(
+            "####..##..#....#..#.###..#....####...##.\n",
+            "#....#..#.#....#..#.#..#.#....#.......#.\n",
+            "###..#....#....####.###..#....###.....#.\n",
+            "#....#.##.#....#..#.#..#.#....#.......#.\n",
+            "#....#..#.#....#..#.#..#.#....#....#..#.\n",
+            "####..###.####.#..#.###..####.#.....##..\n",
+        ));
+        let text = read_screen(&screen);
+        assert_eq!(text.text, "EGLHBLFJ");
+        assert!(text.unknown.is_empty());
+    }
+
+    #[test]
+    fn reports_unknown_glyphs() {
+        let screen = screen(concat!(
+            "#..#.......#\n",
+            "#..#.......#\n",
+            "####.......#\n",
+            "#..#.......#\n",
+            "#..#.......#\n",
+            "#..#.......#\n",
+        ));
+        let text = read_screen(&screen);
+        assert_eq!(text.text, "H ?");
+        assert_eq!(text.unknown, [(2, vec![".#..".to_string(); 6])]);
+        assert_eq!(
+            text.to_string(),
+            "H ?\nunknown glyph at 2:\n.#..\n.#..\n.#..\n.#..\n.#..\n.#.."
+        );
+    }
+}
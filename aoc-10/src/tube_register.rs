@@ -0,0 +1,39 @@
+use std::fmt::{Display, Formatter};
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum TubeRegister {
+    X,
+    Y,
+}
+
+impl TubeRegister {
+    pub const COUNT: usize = 2;
+
+    pub fn index(&self) -> usize {
+        match self {
+            TubeRegister::X => 0,
+            TubeRegister::Y => 1,
+        }
+    }
+}
+
+impl TryFrom<&str> for TubeRegister {
+    type Error = ();
+
+    fn try_from(input: &str) -> Result<Self, Self::Error> {
+        match input {
+            "x" => Ok(TubeRegister::X),
+            "y" => Ok(TubeRegister::Y),
+            _ => Err(()),
+        }
+    }
+}
+
+impl Display for TubeRegister {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TubeRegister::X => write!(f, "x"),
+            TubeRegister::Y => write!(f, "y"),
+        }
+    }
+}
@@ -0,0 +1,133 @@
+use crate::tube_breakpoint::{TubeBreakpoint, TubeComparison};
+use crate::tube_debugger::{TubeDebugger, TubeStop};
+use crate::tube_register::TubeRegister;
+use std::io::{BufRead, Write};
+
+const HELP: &str = "\
+s            step one cycle
+n            step to the next instruction
+c            continue until a breakpoint or the end
+b cycle N    break during cycle N
+b inst N     break at the start of instruction N
+b R OP N     break while register R compares to N, where OP is ==, !=, < or >
+d N          delete breakpoint N
+l            list breakpoints
+r            show registers
+screen       show the screen
+q            quit";
+
+/// Reads debugger commands from `input` until it ends or a `q`, writing the results to `output`.
+pub fn repl<R: BufRead, W: Write>(
+    debugger: &mut TubeDebugger,
+    input: R,
+    mut output: W,
+) -> std::io::Result<()> {
+    for line in input.lines() {
+        let line = line?;
+        let words: Vec<&str> = line.split_whitespace().collect();
+        match words.as_slice() {
+            [] => {}
+            ["q"] => break,
+            ["help"] => writeln!(output, "{}", HELP)?,
+            ["s"] => {
+                debugger.step_cycle();
+                write_state(debugger, &mut output)?;
+            }
+            ["n"] => {
+                debugger.step_instruction();
+                write_state(debugger, &mut output)?;
+            }
+            ["c"] if debugger.is_halted() => writeln!(output, "program has ended")?,
+            ["c"] => {
+                if let TubeStop::Breakpoint(index) = debugger.resume() {
+                    let breakpoint = debugger.breakpoints()[index];
+                    writeln!(output, "breakpoint {}: {}", index, breakpoint)?;
+                }
+                write_state(debugger, &mut output)?;
+            }
+            ["b", args @ ..] => match parse_breakpoint(args) {
+                Some(breakpoint) => {
+                    let index = debugger.add_breakpoint(breakpoint);
+                    writeln!(output, "breakpoint {}: {}", index, breakpoint)?;
+                }
+                None => writeln!(output, "invalid breakpoint, see help")?,
+            },
+            ["d", index] => match index
+                .parse()
+                .ok()
+                .and_then(|i| debugger.remove_breakpoint(i))
+            {
+                Some(breakpoint) => writeln!(output, "deleted {}", breakpoint)?,
+                None => writeln!(output, "no breakpoint {}", index)?,
+            },
+            ["l"] => {
+                for (index, breakpoint) in debugger.breakpoints().iter().enumerate() {
+                    writeln!(output, "breakpoint {}: {}", index, breakpoint)?;
+                }
+            }
+            ["r"] => write_state(debugger, &mut output)?,
+            ["screen"] => write!(output, "{}", debugger.cpu)?,
+            _ => writeln!(output, "unknown command {:?}, see help", line.trim())?,
+        }
+    }
+    Ok(())
+}
+
+fn parse_breakpoint(args: &[&str]) -> Option<TubeBreakpoint> {
+    match args {
+        ["cycle", cycle] => Some(TubeBreakpoint::Cycle(cycle.parse().ok()?)),
+        ["inst", index] => Some(TubeBreakpoint::Instruction(index.parse().ok()?)),
+        [reg, cmp, val] => Some(TubeBreakpoint::Register(
+            TubeRegister::try_from(*reg).ok()?,
+            TubeComparison::try_from(*cmp).ok()?,
+            val.parse().ok()?,
+        )),
+        _ => None,
+    }
+}
+
+fn write_state<W: Write>(debugger: &TubeDebugger, output: &mut W) -> std::io::Result<()> {
+    let cpu = &debugger.cpu;
+    match debugger.current_instruction() {
+        Some(inst) => write!(output, "cycle {}: {} {}", cpu.cycle, cpu.pc, inst)?,
+        None => write!(output, "cycle {}: ended", cpu.cycle)?,
+    }
+    writeln!(
+        output,
+        ", x={} y={}",
+        cpu.register(TubeRegister::X),
+        cpu.register(TubeRegister::Y)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tube_assembler::assemble;
+    use crate::tube_computer::TubeComputer;
+    use crate::tube_debugger::TubeDebugger;
+    use crate::tube_repl::repl;
+
+    #[test]
+    fn runs_commands() {
+        let program = assemble("addy 2\nloop: addx 2\naddy -1\njnz y loop").unwrap();
+        let mut debugger = TubeDebugger::new(TubeComputer::default(), program);
+        let input = "s\nn\nb x == 5\nb cycle\nl\nc\nd 0\nd 0\nc\nc\nwhat\nq\ns\n";
+        let mut output = Vec::new();
+        repl(&mut debugger, input.as_bytes(), &mut output).unwrap();
+        let expected = concat!(
+            "cycle 1: 0 addy 2, x=1 y=0\n",
+            "cycle 3: 1 addx 2, x=1 y=2\n",
+            "breakpoint 0: x == 5\n",
+            "invalid breakpoint, see help\n",
+            "breakpoint 0: x == 5\n",
+            "breakpoint 0: x == 5\n",
+            "cycle 10: 2 addy -1, x=5 y=1\n",
+            "deleted x == 5\n",
+            "no breakpoint 0\n",
+            "cycle 12: ended, x=5 y=0\n",
+            "program has ended\n",
+            "unknown command \"what\", see help\n",
+        );
+        assert_eq!(String::from_utf8(output).unwrap(), expected);
+    }
+}
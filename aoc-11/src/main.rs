@@ -1,8 +1,11 @@
 mod monkey;
 mod monkey_decision;
 mod monkey_expression;
+mod monkey_expression_error;
+mod monkey_expression_parser;
 mod monkey_group;
 mod monkey_item;
+mod monkey_operator;
 
 use crate::monkey_group::MonkeyGroup;
 use aoc_core::puzzle_input::PuzzleInput;
@@ -10,8 +13,11 @@ use aoc_core::puzzle_input::PuzzleInput;
 fn main() {
     let input = PuzzleInput::default();
     let mut monkey_group = MonkeyGroup::from(input.as_string().as_str());
-    for _ in 0..20 {
-        monkey_group.perform_round(true);
+    for round in 1..=20 {
+        if let Err(e) = monkey_group.perform_round(true) {
+            println!("Round {} failed: {}", round, e);
+            return;
+        }
     }
 
     println!(
@@ -20,8 +26,11 @@ fn main() {
     );
 
     let mut monkey_group_no_reduce = MonkeyGroup::from(input.as_string().as_str());
-    for _ in 0..10000 {
-        monkey_group_no_reduce.perform_round(false);
+    for round in 1..=10000 {
+        if let Err(e) = monkey_group_no_reduce.perform_round(false) {
+            println!("Round {} failed: {}", round, e);
+            return;
+        }
     }
     println!(
         "Product of 2 most active monkeys inspections after 10000 rounds: {}",
@@ -1,25 +1,38 @@
 use crate::monkey_decision::MonkeyDecision;
 use crate::monkey_expression::MonkeyExpression;
+use crate::monkey_expression_error::MonkeyExpressionError;
 use crate::monkey_item::MonkeyItem;
 
 pub struct Monkey {
     pub items: Vec<MonkeyItem>,
     pub inspect_count: usize,
-    worry_expression: MonkeyExpression,
+    pub worry_expression: MonkeyExpression,
     pub decision: MonkeyDecision,
 }
 
 impl Monkey {
-    pub fn perform_turn(&mut self, lcm: Option<usize>) -> Vec<(MonkeyItem, usize)> {
-        let mut result = vec![];
-        for mut item in self.items.drain(0..) {
-            self.worry_expression.evaluate_worry(&mut item, lcm);
-            self.inspect_count += 1;
+    /// Inspects every item and returns each with the monkey to throw it to. If the operation fails
+    /// on any item, the monkey inspects none of them and keeps them all.
+    pub fn perform_turn(
+        &mut self,
+        lcm: Option<usize>,
+    ) -> Result<Vec<(MonkeyItem, usize)>, MonkeyExpressionError> {
+        let worries = self
+            .items
+            .iter()
+            .map(|item| self.worry_expression.evaluate_worry(item, lcm))
+            .collect::<Result<Vec<usize>, _>>()?;
+        self.inspect_count += worries.len();
+        self.items.clear();
 
-            let target = self.decision.decide_target(&item);
-            result.push((item, target));
-        }
-        result
+        Ok(worries
+            .into_iter()
+            .map(|value| {
+                let item = MonkeyItem::from(value);
+                let target = self.decision.decide_target(&item);
+                (item, target)
+            })
+            .collect())
     }
 
     pub fn catch(&mut self, item: MonkeyItem) {
@@ -41,7 +54,9 @@ impl From<&str> for Monkey {
             .split(", ")
             .map(|s| s.parse::<usize>().unwrap().into())
             .collect();
-        let worry_expression = lines.next().unwrap().into();
+        let operation = lines.next().unwrap();
+        let worry_expression = MonkeyExpression::try_from(operation)
+            .unwrap_or_else(|e| panic!("Invalid operation {:?}: {}", operation.trim(), e));
         let decision = lines.into();
 
         Monkey {
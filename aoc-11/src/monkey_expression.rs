@@ -1,79 +1,202 @@
+use crate::monkey_expression_error::MonkeyExpressionError;
+use crate::monkey_expression_parser::parse;
 use crate::monkey_item::MonkeyItem;
+use crate::monkey_operator::MonkeyOperator;
+use std::fmt::{Display, Formatter};
 
-pub struct MonkeyExpression {
-    lhs: MonkeyOperand,
-    rhs: MonkeyOperand,
-    op: MonkeyOperator,
+/// How a monkey changes an item's worry level, in terms of the `old` level.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum MonkeyExpression {
+    Old,
+    Value(usize),
+    Binary(MonkeyOperator, Box<MonkeyExpression>, Box<MonkeyExpression>),
 }
 
 impl MonkeyExpression {
-    pub fn evaluate_worry(&self, item: &mut MonkeyItem, lcm_opt: Option<usize>) {
-        item.value = self.op.evaluate(
-            self.lhs.get_value(&item.value),
-            self.rhs.get_value(&item.value),
-        );
+    /// The worry about `item` once a monkey has inspected it: kept below `lcm_opt` if given, or
+    /// else divided by 3.
+    pub fn evaluate_worry(
+        &self,
+        item: &MonkeyItem,
+        lcm_opt: Option<usize>,
+    ) -> Result<usize, MonkeyExpressionError> {
+        let value = self.evaluate(item.value, lcm_opt)?;
+        Ok(match lcm_opt {
+            Some(lcm) => value % lcm,
+            None => (value - (value % 3)) / 3,
+        })
+    }
 
-        if let Some(lcm) = lcm_opt {
-            item.value = item.value % lcm
-        } else {
-            item.value = (item.value - (item.value % 3)) / 3;
+    /// Evaluates the expression for `old`. With a modulus, intermediate results are reduced as in
+    /// [`MonkeyOperator::evaluate`], which fails with [`MonkeyExpressionError::NotReducible`] unless
+    /// [`MonkeyExpression::is_reducible`].
+    pub fn evaluate(
+        &self,
+        old: usize,
+        modulus: Option<usize>,
+    ) -> Result<usize, MonkeyExpressionError> {
+        if modulus.is_some() && !self.is_reducible() {
+            return Err(MonkeyExpressionError::NotReducible);
         }
+        self.evaluate_reduced(old, modulus)
     }
-}
 
-impl From<&str> for MonkeyExpression {
-    fn from(input: &str) -> Self {
-        let mut split = input.split("= ").skip(1).next().unwrap().split(' ');
-        let lhs = split.next().unwrap().into();
-        let op = split.next().unwrap().into();
-        let rhs = split.next().unwrap().into();
-        MonkeyExpression { lhs, op, rhs }
+    /// Whether reducing `old` and every intermediate result modulo some number gives the same
+    /// result modulo that number. That breaks with division or remainder, or an exponent that
+    /// depends on `old`.
+    pub fn is_reducible(&self) -> bool {
+        match self {
+            MonkeyExpression::Old | MonkeyExpression::Value(_) => true,
+            MonkeyExpression::Binary(MonkeyOperator::Div | MonkeyOperator::Rem, _, _) => false,
+            MonkeyExpression::Binary(MonkeyOperator::Pow, lhs, rhs) => {
+                lhs.is_reducible() && rhs.is_constant()
+            }
+            MonkeyExpression::Binary(_, lhs, rhs) => lhs.is_reducible() && rhs.is_reducible(),
+        }
     }
-}
 
-pub enum MonkeyOperand {
-    Old,
-    Value(usize),
-}
+    fn is_constant(&self) -> bool {
+        match self {
+            MonkeyExpression::Old => false,
+            MonkeyExpression::Value(_) => true,
+            MonkeyExpression::Binary(_, lhs, rhs) => lhs.is_constant() && rhs.is_constant(),
+        }
+    }
 
-impl MonkeyOperand {
-    pub fn get_value(&self, item: &usize) -> usize {
+    fn evaluate_reduced(
+        &self,
+        old: usize,
+        modulus: Option<usize>,
+    ) -> Result<usize, MonkeyExpressionError> {
         match self {
-            MonkeyOperand::Old => *item,
-            MonkeyOperand::Value(v) => *v,
+            MonkeyExpression::Old => Ok(old),
+            MonkeyExpression::Value(v) => Ok(*v),
+            // Exponents are constant when reducing, so work them out in full.
+            MonkeyExpression::Binary(MonkeyOperator::Pow, lhs, rhs) => MonkeyOperator::Pow
+                .evaluate(
+                    lhs.evaluate_reduced(old, modulus)?,
+                    rhs.evaluate_reduced(old, None)?,
+                    modulus,
+                ),
+            MonkeyExpression::Binary(op, lhs, rhs) => op.evaluate(
+                lhs.evaluate_reduced(old, modulus)?,
+                rhs.evaluate_reduced(old, modulus)?,
+                modulus,
+            ),
         }
     }
-}
 
-impl From<&str> for MonkeyOperand {
-    fn from(input: &str) -> Self {
-        match input {
-            "old" => MonkeyOperand::Old,
-            _ => MonkeyOperand::Value(input.parse().unwrap()),
+    /// Writes `child` as an operand of `op`, with parentheses if it would otherwise bind
+    /// differently.
+    fn fmt_operand(
+        f: &mut Formatter<'_>,
+        op: &MonkeyOperator,
+        child: &MonkeyExpression,
+        is_rhs: bool,
+    ) -> std::fmt::Result {
+        let needs_parentheses = match child {
+            MonkeyExpression::Binary(inner, _, _) => {
+                inner.precedence() < op.precedence()
+                    || (inner.precedence() == op.precedence()
+                        && is_rhs != op.is_right_associative())
+            }
+            _ => false,
+        };
+        match needs_parentheses {
+            true => write!(f, "({})", child),
+            false => write!(f, "{}", child),
         }
     }
 }
 
-pub enum MonkeyOperator {
-    Add,
-    Mul,
+impl TryFrom<&str> for MonkeyExpression {
+    type Error = MonkeyExpressionError;
+
+    fn try_from(input: &str) -> Result<Self, Self::Error> {
+        parse(input)
+    }
 }
 
-impl From<&str> for MonkeyOperator {
-    fn from(input: &str) -> Self {
-        match input {
-            "*" => MonkeyOperator::Mul,
-            "+" => MonkeyOperator::Add,
-            _a => panic!("{} is not an operator!", _a),
+impl Display for MonkeyExpression {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MonkeyExpression::Old => write!(f, "old"),
+            MonkeyExpression::Value(v) => write!(f, "{}", v),
+            MonkeyExpression::Binary(op, lhs, rhs) => {
+                MonkeyExpression::fmt_operand(f, op, lhs, false)?;
+                write!(f, " {} ", op)?;
+                MonkeyExpression::fmt_operand(f, op, rhs, true)
+            }
         }
     }
 }
 
-impl MonkeyOperator {
-    pub fn evaluate(&self, lhs: usize, rhs: usize) -> usize {
-        match self {
-            MonkeyOperator::Add => lhs + rhs,
-            MonkeyOperator::Mul => lhs * rhs,
+#[cfg(test)]
+mod tests {
+    use crate::monkey_expression::MonkeyExpression;
+    use crate::monkey_expression_error::MonkeyExpressionError;
+    use crate::monkey_item::MonkeyItem;
+
+    fn expression_of(input: &str) -> MonkeyExpression {
+        MonkeyExpression::try_from(input).unwrap()
+    }
+
+    #[test]
+    fn evaluates() {
+        let expression = expression_of("new = (old - 1) * old % 7 + 2 ^ old / 2");
+        assert_eq!(expression.evaluate(5, None), Ok(6 + 16));
+        assert_eq!(
+            expression_of("new = old / (old - 5)").evaluate(5, None),
+            Err(MonkeyExpressionError::DivisionByZero)
+        );
+    }
+
+    #[test]
+    fn reduces_worry() {
+        let expression = expression_of("new = old * old * old + 1");
+        assert_eq!(
+            expression.evaluate_worry(&MonkeyItem::from(20), None),
+            Ok(2667)
+        );
+
+        let lcm = 7 * 11;
+        assert_eq!(
+            expression.evaluate_worry(&MonkeyItem::from(3_000_000), Some(lcm)),
+            Ok(((3_000_000u128.pow(3) + 1) % lcm as u128) as usize)
+        );
+    }
+
+    #[test]
+    fn reduced_agrees_with_unreduced() {
+        let modulus = 2 * 3 * 5 * 7;
+        for input in [
+            "new = old * 19",
+            "new = (old - 1) * old + 3 ^ 4",
+            "new = old ^ (10 / 3) + old * old",
+        ] {
+            let expression = expression_of(input);
+            assert!(expression.is_reducible());
+            for old in [1, 6, 97, 1000, 123_456] {
+                let unreduced = expression.evaluate(old, None).unwrap() % modulus;
+                let reduced = expression.evaluate(old % modulus, Some(modulus)).unwrap();
+                assert_eq!(reduced, unreduced, "{} for {}", input, old);
+            }
         }
     }
+
+    #[test]
+    fn refuses_to_reduce_division() {
+        for input in ["new = old * 10 / 4", "new = old % 5", "new = 2 ^ (old + 0)"] {
+            let expression = expression_of(input);
+            assert!(!expression.is_reducible());
+            assert_eq!(
+                expression.evaluate(4, Some(7)),
+                Err(MonkeyExpressionError::NotReducible)
+            );
+        }
+        assert_eq!(
+            expression_of("new = old * 10 / 4").evaluate(4, None),
+            Ok(10)
+        );
+    }
 }
@@ -0,0 +1,54 @@
+use std::fmt::{Display, Formatter};
+
+/// A problem found while parsing or evaluating an operation. Columns start at 1.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum MonkeyExpressionError {
+    MissingAssignment,
+    UnexpectedCharacter { column: usize, value: char },
+    InvalidNumber { column: usize, value: String },
+    ExpectedOperand { column: usize, found: String },
+    ExpectedOperator { column: usize, found: String },
+    UnclosedParenthesis { column: usize },
+    UnexpectedEnd,
+    DivisionByZero,
+    Negative,
+    Overflow,
+    NotReducible,
+}
+
+impl Display for MonkeyExpressionError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MonkeyExpressionError::MissingAssignment => write!(f, "expected \"new = ...\""),
+            MonkeyExpressionError::UnexpectedCharacter { column, value } => {
+                write!(f, "column {}: unexpected character {:?}", column, value)
+            }
+            MonkeyExpressionError::InvalidNumber { column, value } => {
+                write!(f, "column {}: invalid number {:?}", column, value)
+            }
+            MonkeyExpressionError::ExpectedOperand { column, found } => write!(
+                f,
+                "column {}: expected old, a number or '(', found {:?}",
+                column, found
+            ),
+            MonkeyExpressionError::ExpectedOperator { column, found } => {
+                write!(
+                    f,
+                    "column {}: expected an operator, found {:?}",
+                    column, found
+                )
+            }
+            MonkeyExpressionError::UnclosedParenthesis { column } => {
+                write!(f, "column {}: '(' is never closed", column)
+            }
+            MonkeyExpressionError::UnexpectedEnd => write!(f, "expression ends too early"),
+            MonkeyExpressionError::DivisionByZero => write!(f, "division by zero"),
+            MonkeyExpressionError::Negative => write!(f, "worry would become negative"),
+            MonkeyExpressionError::Overflow => write!(f, "worry is too large"),
+            MonkeyExpressionError::NotReducible => write!(
+                f,
+                "worry can't be kept small through division, remainder or an exponent of old"
+            ),
+        }
+    }
+}
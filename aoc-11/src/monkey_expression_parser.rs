@@ -0,0 +1,218 @@
+use crate::monkey_expression::MonkeyExpression;
+use crate::monkey_expression_error::MonkeyExpressionError;
+use crate::monkey_operator::MonkeyOperator;
+use std::fmt::{Display, Formatter};
+use std::iter::Peekable;
+use std::vec::IntoIter;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum MonkeyToken {
+    Old,
+    Value(usize),
+    Operator(MonkeyOperator),
+    Open,
+    Close,
+}
+
+impl Display for MonkeyToken {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MonkeyToken::Old => write!(f, "old"),
+            MonkeyToken::Value(v) => write!(f, "{}", v),
+            MonkeyToken::Operator(op) => write!(f, "{}", op),
+            MonkeyToken::Open => write!(f, "("),
+            MonkeyToken::Close => write!(f, ")"),
+        }
+    }
+}
+
+/// Parses the right-hand side of an `Operation: new = ...` line. Errors point at columns of the
+/// whole line.
+pub fn parse(input: &str) -> Result<MonkeyExpression, MonkeyExpressionError> {
+    let (assignee, rhs) = input
+        .split_once('=')
+        .ok_or(MonkeyExpressionError::MissingAssignment)?;
+    if !assignee.trim_end().ends_with("new") {
+        return Err(MonkeyExpressionError::MissingAssignment);
+    }
+    let offset = assignee.chars().count() + 1;
+    let mut tokens = tokenize(rhs, offset)?.into_iter().peekable();
+
+    let expression = parse_expression(&mut tokens, 0)?;
+    match tokens.next() {
+        None => Ok(expression),
+        Some((column, token)) => Err(MonkeyExpressionError::ExpectedOperator {
+            column,
+            found: token.to_string(),
+        }),
+    }
+}
+
+/// Splits `input` into tokens, each with its column. The first character is at `offset + 1`.
+fn tokenize(
+    input: &str,
+    offset: usize,
+) -> Result<Vec<(usize, MonkeyToken)>, MonkeyExpressionError> {
+    let mut tokens = Vec::new();
+    let mut chars = input.chars().enumerate().peekable();
+    while let Some((i, c)) = chars.next() {
+        let column = offset + i + 1;
+        let token = match c {
+            '(' => MonkeyToken::Open,
+            ')' => MonkeyToken::Close,
+            _ if c.is_whitespace() => continue,
+            _ if c.is_ascii_alphanumeric() => {
+                let mut word = c.to_string();
+                while let Some((_, c)) = chars.next_if(|(_, c)| c.is_ascii_alphanumeric()) {
+                    word.push(c);
+                }
+                match word.as_str() {
+                    "old" => MonkeyToken::Old,
+                    _ if c.is_ascii_digit() => match word.parse() {
+                        Ok(value) => MonkeyToken::Value(value),
+                        Err(_) => {
+                            return Err(MonkeyExpressionError::InvalidNumber {
+                                column,
+                                value: word,
+                            })
+                        }
+                    },
+                    _ => {
+                        return Err(MonkeyExpressionError::ExpectedOperand {
+                            column,
+                            found: word,
+                        })
+                    }
+                }
+            }
+            _ => match MonkeyOperator::try_from(c) {
+                Ok(op) => MonkeyToken::Operator(op),
+                Err(_) => {
+                    return Err(MonkeyExpressionError::UnexpectedCharacter { column, value: c })
+                }
+            },
+        };
+        tokens.push((column, token));
+    }
+    Ok(tokens)
+}
+
+/// Parses operands joined by operators of at least `min_precedence`, by precedence climbing.
+fn parse_expression(
+    tokens: &mut Peekable<IntoIter<(usize, MonkeyToken)>>,
+    min_precedence: u8,
+) -> Result<MonkeyExpression, MonkeyExpressionError> {
+    let mut lhs = parse_operand(tokens)?;
+    while let Some((_, MonkeyToken::Operator(op))) = tokens.peek().copied() {
+        if op.precedence() < min_precedence {
+            break;
+        }
+        tokens.next();
+        let next_precedence = match op.is_right_associative() {
+            true => op.precedence(),
+            false => op.precedence() + 1,
+        };
+        let rhs = parse_expression(tokens, next_precedence)?;
+        lhs = MonkeyExpression::Binary(op, Box::new(lhs), Box::new(rhs));
+    }
+    Ok(lhs)
+}
+
+fn parse_operand(
+    tokens: &mut Peekable<IntoIter<(usize, MonkeyToken)>>,
+) -> Result<MonkeyExpression, MonkeyExpressionError> {
+    match tokens.next() {
+        Some((_, MonkeyToken::Old)) => Ok(MonkeyExpression::Old),
+        Some((_, MonkeyToken::Value(v))) => Ok(MonkeyExpression::Value(v)),
+        Some((open, MonkeyToken::Open)) => {
+            let inner = parse_expression(tokens, 0)?;
+            match tokens.next() {
+                Some((_, MonkeyToken::Close)) => Ok(inner),
+                Some((column, token)) => Err(MonkeyExpressionError::ExpectedOperator {
+                    column,
+                    found: token.to_string(),
+                }),
+                None => Err(MonkeyExpressionError::UnclosedParenthesis { column: open }),
+            }
+        }
+        Some((column, token)) => Err(MonkeyExpressionError::ExpectedOperand {
+            column,
+            found: token.to_string(),
+        }),
+        None => Err(MonkeyExpressionError::UnexpectedEnd),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::monkey_expression_error::MonkeyExpressionError;
+    use crate::monkey_expression_parser::parse;
+
+    fn error_of(input: &str) -> MonkeyExpressionError {
+        parse(input).err().unwrap()
+    }
+
+    #[test]
+    fn parses_with_precedence() {
+        let display = |input| parse(input).unwrap().to_string();
+        assert_eq!(display("  Operation: new = old * 19"), "old * 19");
+        assert_eq!(display("new = old+3*old"), "old + 3 * old");
+        assert_eq!(display("new = (old + 3) * old"), "(old + 3) * old");
+        assert_eq!(display("new = old - (3 - 2)"), "old - (3 - 2)");
+        assert_eq!(display("new = old - 3 - 2"), "old - 3 - 2");
+        assert_eq!(display("new = 2 ^ 3 ^ 2"), "2 ^ 3 ^ 2");
+        assert_eq!(display("new = (2 ^ 3) ^ 2"), "(2 ^ 3) ^ 2");
+        assert_eq!(display("new = ((old))"), "old");
+    }
+
+    #[test]
+    fn reports_errors() {
+        assert_eq!(
+            error_of("old * 2"),
+            MonkeyExpressionError::MissingAssignment
+        );
+        assert_eq!(
+            error_of("new = old & 2"),
+            MonkeyExpressionError::UnexpectedCharacter {
+                column: 11,
+                value: '&'
+            }
+        );
+        assert_eq!(
+            error_of("new = old * 99999999999999999999"),
+            MonkeyExpressionError::InvalidNumber {
+                column: 13,
+                value: "99999999999999999999".to_string()
+            }
+        );
+        assert_eq!(
+            error_of("new = old * new"),
+            MonkeyExpressionError::ExpectedOperand {
+                column: 13,
+                found: "new".to_string()
+            }
+        );
+        assert_eq!(
+            error_of("new = old * * 2"),
+            MonkeyExpressionError::ExpectedOperand {
+                column: 13,
+                found: "*".to_string()
+            }
+        );
+        assert_eq!(
+            error_of("new = old 2"),
+            MonkeyExpressionError::ExpectedOperator {
+                column: 11,
+                found: "2".to_string()
+            }
+        );
+        assert_eq!(
+            error_of("new = (old + 2"),
+            MonkeyExpressionError::UnclosedParenthesis { column: 7 }
+        );
+        assert_eq!(
+            error_of("new = old +"),
+            MonkeyExpressionError::UnexpectedEnd
+        );
+    }
+}
@@ -1,4 +1,5 @@
 use crate::monkey::Monkey;
+use crate::monkey_expression_error::MonkeyExpressionError;
 
 pub struct MonkeyGroup {
     monkeys: Vec<Monkey>,
@@ -6,17 +7,29 @@ pub struct MonkeyGroup {
 }
 
 impl MonkeyGroup {
-    pub fn perform_round(&mut self, reduce_worry: bool) {
+    /// Lets each monkey take a turn. Without `reduce_worry`, worry is kept small by the product of
+    /// the monkeys' divisors instead, which fails up front unless every operation is reducible; see
+    /// [`MonkeyExpression::is_reducible`](crate::monkey_expression::MonkeyExpression::is_reducible).
+    /// If an operation fails partway, the round stops before that monkey's turn.
+    pub fn perform_round(&mut self, reduce_worry: bool) -> Result<(), MonkeyExpressionError> {
         let lcm = match reduce_worry {
             true => None,
-            false => Some(self.lcm),
+            false if self.is_reducible() => Some(self.lcm),
+            false => return Err(MonkeyExpressionError::NotReducible),
         };
         for i in 0..self.monkeys.len() {
-            let thrown_items = self.monkeys[i].perform_turn(lcm);
+            let thrown_items = self.monkeys[i].perform_turn(lcm)?;
             for (item, target) in thrown_items {
                 self.monkeys[target].catch(item);
             }
         }
+        Ok(())
+    }
+
+    fn is_reducible(&self) -> bool {
+        self.monkeys
+            .iter()
+            .all(|x| x.worry_expression.is_reducible())
     }
 
     pub fn most_active_score(&self) -> usize {
@@ -36,3 +49,62 @@ impl From<&str> for MonkeyGroup {
         MonkeyGroup { monkeys, lcm }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::monkey_expression_error::MonkeyExpressionError;
+    use crate::monkey_group::MonkeyGroup;
+
+    fn group_with(operation: &str) -> MonkeyGroup {
+        MonkeyGroup::from(
+            format!(
+                "Monkey 0:
+  Starting items: 79, 98
+  Operation: new = old * 19
+  Test: divisible by 23
+    If true: throw to monkey 1
+    If false: throw to monkey 1
+
+Monkey 1:
+  Starting items: 54, 3
+  Operation: {}
+  Test: divisible by 19
+    If true: throw to monkey 0
+    If false: throw to monkey 0",
+                operation
+            )
+            .as_str(),
+        )
+    }
+
+    #[test]
+    fn performs_rounds() {
+        let mut group = group_with("new = old + 6");
+        assert_eq!(group.perform_round(false), Ok(()));
+        assert_eq!(group.perform_round(true), Ok(()));
+        assert_eq!(group.most_active_score(), 6 * 8);
+    }
+
+    #[test]
+    fn rejects_rules_that_cant_be_reduced() {
+        let mut group = group_with("new = old / 2");
+        assert_eq!(
+            group.perform_round(false),
+            Err(MonkeyExpressionError::NotReducible)
+        );
+        assert_eq!(group.most_active_score(), 0);
+        assert_eq!(group.perform_round(true), Ok(()));
+    }
+
+    #[test]
+    fn stops_when_worry_turns_negative() {
+        let mut group = group_with("new = old - 60");
+        assert_eq!(
+            group.perform_round(true),
+            Err(MonkeyExpressionError::Negative)
+        );
+        assert_eq!(group.monkeys[0].inspect_count, 2);
+        assert_eq!(group.monkeys[1].inspect_count, 0);
+        assert_eq!(group.monkeys[1].items.len(), 4);
+    }
+}
@@ -0,0 +1,147 @@
+use crate::monkey_expression_error::MonkeyExpressionError;
+use std::fmt::{Display, Formatter, Write};
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum MonkeyOperator {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Rem,
+    Pow,
+}
+
+impl MonkeyOperator {
+    /// Operators with a higher precedence bind more tightly.
+    pub fn precedence(&self) -> u8 {
+        match self {
+            MonkeyOperator::Add | MonkeyOperator::Sub => 1,
+            MonkeyOperator::Mul | MonkeyOperator::Div | MonkeyOperator::Rem => 2,
+            MonkeyOperator::Pow => 3,
+        }
+    }
+
+    /// Whether `a op b op c` means `a op (b op c)`.
+    pub fn is_right_associative(&self) -> bool {
+        matches!(self, MonkeyOperator::Pow)
+    }
+
+    /// Applies the operator. With a modulus, additions, subtractions, multiplications and powers
+    /// are taken modulo it, which keeps every result small, with the exponent taken as given.
+    /// Division and remainder don't work modulo a number, so they fail with a modulus.
+    pub fn evaluate(
+        &self,
+        lhs: usize,
+        rhs: usize,
+        modulus: Option<usize>,
+    ) -> Result<usize, MonkeyExpressionError> {
+        if let Some(m) = modulus {
+            let (l, r, m) = ((lhs % m) as u128, (rhs % m) as u128, m as u128);
+            let result = match self {
+                MonkeyOperator::Add => (l + r) % m,
+                MonkeyOperator::Sub => (l + m - r) % m,
+                MonkeyOperator::Mul => l * r % m,
+                MonkeyOperator::Pow => mod_pow(l, rhs, m),
+                MonkeyOperator::Div | MonkeyOperator::Rem => {
+                    return Err(MonkeyExpressionError::NotReducible)
+                }
+            };
+            return Ok(result as usize);
+        }
+
+        match self {
+            MonkeyOperator::Add => lhs.checked_add(rhs).ok_or(MonkeyExpressionError::Overflow),
+            MonkeyOperator::Sub => lhs.checked_sub(rhs).ok_or(MonkeyExpressionError::Negative),
+            MonkeyOperator::Mul => lhs.checked_mul(rhs).ok_or(MonkeyExpressionError::Overflow),
+            MonkeyOperator::Div => lhs
+                .checked_div(rhs)
+                .ok_or(MonkeyExpressionError::DivisionByZero),
+            MonkeyOperator::Rem => lhs
+                .checked_rem(rhs)
+                .ok_or(MonkeyExpressionError::DivisionByZero),
+            MonkeyOperator::Pow => u32::try_from(rhs)
+                .ok()
+                .and_then(|exponent| lhs.checked_pow(exponent))
+                .ok_or(MonkeyExpressionError::Overflow),
+        }
+    }
+}
+
+/// `base ^ exponent % modulus`, by repeated squaring.
+fn mod_pow(mut base: u128, mut exponent: usize, modulus: u128) -> u128 {
+    let mut result = 1 % modulus;
+    while exponent > 0 {
+        if exponent % 2 == 1 {
+            result = result * base % modulus;
+        }
+        base = base * base % modulus;
+        exponent /= 2;
+    }
+    result
+}
+
+impl TryFrom<char> for MonkeyOperator {
+    type Error = ();
+
+    fn try_from(input: char) -> Result<Self, Self::Error> {
+        match input {
+            '+' => Ok(MonkeyOperator::Add),
+            '-' => Ok(MonkeyOperator::Sub),
+            '*' => Ok(MonkeyOperator::Mul),
+            '/' => Ok(MonkeyOperator::Div),
+            '%' => Ok(MonkeyOperator::Rem),
+            '^' => Ok(MonkeyOperator::Pow),
+            _ => Err(()),
+        }
+    }
+}
+
+impl Display for MonkeyOperator {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let c = match self {
+            MonkeyOperator::Add => '+',
+            MonkeyOperator::Sub => '-',
+            MonkeyOperator::Mul => '*',
+            MonkeyOperator::Div => '/',
+            MonkeyOperator::Rem => '%',
+            MonkeyOperator::Pow => '^',
+        };
+        f.write_char(c)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::monkey_expression_error::MonkeyExpressionError;
+    use crate::monkey_operator::MonkeyOperator;
+
+    #[test]
+    fn evaluates() {
+        assert_eq!(MonkeyOperator::Sub.evaluate(7, 3, None), Ok(4));
+        assert_eq!(MonkeyOperator::Rem.evaluate(7, 3, None), Ok(1));
+        assert_eq!(MonkeyOperator::Pow.evaluate(2, 10, None), Ok(1024));
+        assert_eq!(
+            MonkeyOperator::Sub.evaluate(3, 7, None),
+            Err(MonkeyExpressionError::Negative)
+        );
+        assert_eq!(
+            MonkeyOperator::Div.evaluate(3, 0, None),
+            Err(MonkeyExpressionError::DivisionByZero)
+        );
+        assert_eq!(
+            MonkeyOperator::Pow.evaluate(10, 100, None),
+            Err(MonkeyExpressionError::Overflow)
+        );
+    }
+
+    #[test]
+    fn evaluates_with_modulus() {
+        assert_eq!(MonkeyOperator::Sub.evaluate(3, 7, Some(10)), Ok(6));
+        assert_eq!(MonkeyOperator::Mul.evaluate(usize::MAX, 2, Some(7)), Ok(2));
+        assert_eq!(MonkeyOperator::Pow.evaluate(10, 100, Some(7)), Ok(4));
+        assert_eq!(
+            MonkeyOperator::Div.evaluate(30, 4, Some(7)),
+            Err(MonkeyExpressionError::NotReducible)
+        );
+    }
+}